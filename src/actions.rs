@@ -0,0 +1,200 @@
+use base64::Engine;
+use crypto_box::aead::OsRng;
+
+// GitHub Actions secrets & variables of repo, environment or org
+
+pub const org_visibilities: [&str; 3] = ["all", "private", "selected"];
+// GitHub refuses bigger secrets
+pub const secret_max_size: usize = 48 * 1024;
+
+#[derive(Debug, Clone)]
+pub enum ActionsScope {
+    Repo { owner: String, repo_name: String },
+    Environment { owner: String, repo_name: String, environment: String },
+    Org { org: String, visibility: String, selected_repository_ids: Vec<u64> },
+}
+
+impl ActionsScope {
+    // kind is "secrets" or "variables"
+    pub fn path(&self, kind: &str) -> String {
+        match self {
+            ActionsScope::Repo { owner, repo_name } => format!("/repos/{owner}/{repo_name}/actions/{kind}"),
+            ActionsScope::Environment { owner, repo_name, environment } => format!(
+                "/repos/{owner}/{repo_name}/environments/{environment}/{kind}",
+                environment = super::url_path_segment(environment),
+            ),
+            ActionsScope::Org { org, .. } => format!("/orgs/{org}/actions/{kind}"),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            ActionsScope::Repo { owner, repo_name } => format!("{owner}/{repo_name} repo"),
+            ActionsScope::Environment { owner, repo_name, environment } => format!("{environment} environment of {owner}/{repo_name} repo"),
+            ActionsScope::Org { org, .. } => format!("{org} org"),
+        }
+    }
+
+    // Which repos of org can use secret or variable, sent along with its value
+    pub fn extend_body(&self, body: &mut serde_json::Value) {
+        if let ActionsScope::Org { visibility, selected_repository_ids, .. } = self {
+            body["visibility"] = serde_json::json!(visibility);
+            if visibility == "selected" {
+                body["selected_repository_ids"] = serde_json::json!(selected_repository_ids);
+            }
+        }
+    }
+}
+
+// Letters, digits & underscores, not starting with a digit or GITHUB_
+pub fn name_is_valid(name: &str) -> bool {
+    let starts_valid = name.chars().next().map(|c| c.is_ascii_alphabetic() || c == '_').unwrap_or(false);
+    starts_valid
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.to_ascii_uppercase().starts_with("GITHUB_")
+}
+
+// libsodium sealed box with base64 public key from GET .../secrets/public-key, base64 result for PUT .../secrets/{name}
+pub fn seal(public_key_base64: &str, value: &str) -> Result<String, String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let public_key_bytes = engine.decode(public_key_base64).map_err(|e| format!("invalid public key, error: {e}"))?;
+    let public_key = crypto_box::PublicKey::from_slice(&public_key_bytes).map_err(|e| format!("invalid public key, error: {e}"))?;
+    let sealed = public_key.seal(&mut OsRng, value.as_bytes()).map_err(|e| format!("failed to encrypt, error: {e}"))?;
+    Ok(engine.encode(sealed))
+}
+
+// Text up to closing quote, None when there is none yet. Double quoted \n, \" & \\ are unescaped in one pass,
+// other escapes are kept as-is
+fn dotenv_unquote(text: &str, quote: char) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c == quote => return Some(value),
+            '\\' if quote == '"' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some(escaped @ ('"' | '\\')) => value.push(escaped),
+                Some(other) => {
+                    value.push('\\');
+                    value.push(other);
+                },
+                None => value.push('\\'),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+// KEY=VALUE lines, "export " prefix, # comments, single quotes are literal, double quotes support \n escapes
+pub fn dotenv_parse(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut entries: Vec<(String, String)> = vec![];
+    let mut lines = text.lines().enumerate();
+    while let Some((line_index, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        let (name, value_raw) = match line.split_once('=') {
+            Some((name, value_raw)) => (name.trim(), value_raw.trim()),
+            None => return Err(format!("line {line_number} is not KEY=VALUE", line_number = line_index + 1)),
+        };
+        let quote_opt = value_raw.chars().next().filter(|c| *c == '"' || *c == '\'');
+        let value = match quote_opt {
+            Some(quote) => {
+                // quoted values may span lines
+                let mut value_quoted = value_raw[1..].to_string();
+                loop {
+                    if let Some(value) = dotenv_unquote(&value_quoted, quote) {
+                        break value;
+                    }
+                    match lines.next() {
+                        Some((_, next_line)) => {
+                            value_quoted.push('\n');
+                            value_quoted.push_str(next_line);
+                        },
+                        None => return Err(format!("unclosed quote of {name} on line {line_number}", line_number = line_index + 1)),
+                    }
+                }
+            },
+            // unquoted values end at inline comment
+            None => match value_raw.find(" #") {
+                Some(comment_index) => value_raw[..comment_index].trim_end().to_string(),
+                None => value_raw.to_string(),
+            },
+        };
+        match entries.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((name.to_string(), value)),
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_is_valid_rules() {
+        assert!(name_is_valid("API_TOKEN"));
+        assert!(name_is_valid("_private2"));
+        assert!(!name_is_valid(""));
+        assert!(!name_is_valid("2FA_KEY"));
+        assert!(!name_is_valid("API-TOKEN"));
+        assert!(!name_is_valid("github_token"));
+    }
+
+    #[test]
+    fn dotenv_parse_plain_and_quoted() {
+        let text = "# comment\nexport A=1\nB = two words # inline\nC='single \\n kept'\nD=\"line\\nbreak\"\nA=override\n";
+        let entries = dotenv_parse(text).expect("valid dotenv");
+        assert_eq!(entries, vec![
+            ("A".to_string(), "override".to_string()),
+            ("B".to_string(), "two words".to_string()),
+            ("C".to_string(), "single \\n kept".to_string()),
+            ("D".to_string(), "line\nbreak".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn dotenv_parse_escapes_in_one_pass() {
+        let entries = dotenv_parse(r#"PATH_WIN="C:\\new"
+QUOTED="say \"hi\""
+TRAILING="abc\\"
+UNKNOWN="a\tb"
+"#).expect("valid dotenv");
+        assert_eq!(entries, vec![
+            ("PATH_WIN".to_string(), r"C:\new".to_string()),
+            ("QUOTED".to_string(), r#"say "hi""#.to_string()),
+            ("TRAILING".to_string(), r"abc\".to_string()),
+            ("UNKNOWN".to_string(), r"a\tb".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn dotenv_parse_multiline_and_errors() {
+        let entries = dotenv_parse("KEY=\"-----BEGIN KEY-----\nabc\n-----END KEY-----\" # pem\nNEXT=1\n").expect("valid dotenv");
+        assert_eq!(entries[0].1, "-----BEGIN KEY-----\nabc\n-----END KEY-----");
+        assert_eq!(entries[1], ("NEXT".to_string(), "1".to_string()));
+
+        assert!(dotenv_parse("KEY=\"never closed\nNEXT=1\n").is_err());
+        assert!(dotenv_parse("ESCAPED_END=\"abc\\\"\n").is_err());
+        assert!(dotenv_parse("NOT A PAIR\n").is_err());
+    }
+
+    #[test]
+    fn seal_round_trip() {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let secret_key = crypto_box::SecretKey::generate(&mut OsRng);
+        let public_key_base64 = engine.encode(secret_key.public_key().as_bytes());
+
+        let sealed_base64 = seal(&public_key_base64, "hunter2").expect("can seal");
+        let sealed = engine.decode(sealed_base64).expect("valid base64");
+        assert_eq!(secret_key.unseal(&sealed).expect("can unseal"), b"hunter2");
+
+        assert!(seal("not base64!", "hunter2").is_err());
+        assert!(seal(&engine.encode([0u8; 8]), "hunter2").is_err());
+    }
+}
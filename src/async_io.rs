@@ -1,5 +1,5 @@
 use std::{fmt, io};
-use tokio::io::{stdout, stdin, BufReader, AsyncWriteExt, AsyncBufReadExt};
+use tokio::io::{stdout, stdin, BufReader, AsyncWriteExt, AsyncBufReadExt, AsyncReadExt};
 pub use tokio::fs;
 pub use regex::Regex;
 pub use async_std::path as path;
@@ -188,6 +188,16 @@ pub async fn stdin_read_line() -> String {
     line
 }
 
+// Piped input, None when stdin is a terminal, InvalidData error when it's not UTF-8
+pub async fn stdin_read_piped() -> io::Result<Option<String>> {
+    if io::IsTerminal::is_terminal(&io::stdin()) {
+        return Ok(None);
+    }
+    let mut text = String::new();
+    stdin().read_to_string(&mut text).await?;
+    Ok(Some(text))
+}
+
 pub async fn prompt(text: &str, default: Option<&str>) -> String {
     let _ = match default {
         Some(default_verbose) => aprint!("{text} [{default_verbose}]: "),
@@ -202,6 +212,13 @@ pub async fn prompt(text: &str, default: Option<&str>) -> String {
     answer
 }
 
+// Typed answer is not echoed, for secrets
+pub async fn prompt_hidden(text: &str) -> String {
+    let text = format!("{text}: ");
+    let answer = tokio::task::spawn_blocking(move || rpassword::prompt_password(text)).await.expect("can join prompt");
+    answer.expect("can read line")
+}
+
 pub async fn prompt_bool(text: &str, default: Option<bool>) -> bool {
     loop {
         let _ = match default {
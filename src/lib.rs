@@ -6,7 +6,7 @@ use terminal_size::terminal_size;
 use configparser::ini::Ini;
 use serde::{Serialize, Deserialize};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     ops::Deref,
 };
 use thiserror::Error;
@@ -46,7 +46,10 @@ enum GhRsError {
     
     #[error("Git management (git2) error: {0}")]
     Git2Error(#[from] git2::Error),
-    
+
+    #[error("HTTP (reqwest) error: {0}")]
+    ReqwestError(#[from] reqwest::Error),
+
     #[error("Command processing error: {0}")]
     CmdError(String),
 }
@@ -59,6 +62,18 @@ macro_rules! err_from {
     ($($arg:tt)*) => (Err(GhRsError::from($($arg)*)))
 }
 
+mod lfs;
+mod project;
+mod secrets;
+mod rewrite;
+mod scaffold;
+mod template;
+mod protect;
+mod settings;
+mod actions;
+#[cfg(test)]
+mod test_git;
+
 fn get_hr() -> String { 
     "─".repeat(
         terminal_size()
@@ -76,6 +91,14 @@ async fn get_github_config_from_machine() -> Option<Ini> {
     }
 }
 
+async fn get_gh_rs_config() -> Option<Ini> {
+    let mut config = Ini::new_cs();
+    match config.load_async(path!("~/.config/gh.rs/config.ini")).await {
+        Ok(r) => Some(config),
+        Err(e) => None
+    }
+}
+
 async fn get_github_signature_from_machine<'s>() -> Option<git2::Signature<'s>> {
     if let Some(config) = get_github_config_from_machine().await {
         if let Some(name) = config.get("user", "name") {
@@ -89,6 +112,27 @@ async fn get_github_signature_from_machine<'s>() -> Option<git2::Signature<'s>>
     None
 }
 
+async fn get_signature<'s>(
+    user: &octocrab::models::Author,
+) -> GhRsResult<git2::Signature<'s>> {
+    let sig = match get_github_signature_from_machine().await {
+        Some(sig_found) => sig_found,
+        None => {
+            if user.email.is_some() {
+                match git2::Signature::now(
+                    user.login.clone().as_str(), 
+                    user.email.clone().expect("must be some").as_str()
+                ) {
+                    Ok(s) => s,
+                    Err(e) => return_cmd_err!("Failed to create signature, error: {e}"),
+                }
+            } else {
+                return_cmd_err!("Failed to find signature");
+            }
+        },
+    };
+    Ok(sig)
+}
 
 async fn get_github_token_from_machine(
     tried_env: &mut bool, 
@@ -204,1075 +248,5607 @@ async fn get_or_create_ssh_key(
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabReposCreateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_issues: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_projects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_wiki: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_discussions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_template: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_merge_commit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_squash_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_rebase_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_branch_on_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_auto_merge: Option<bool>,
+    // "public", "private" or "internal" (enterprise orgs), takes precedence over "private"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    // not accepted on create, applied with update after it
+    #[serde(skip)]
+    pub secret_scanning: Option<bool>,
+    #[serde(skip)]
+    pub secret_scanning_push_protection: Option<bool>,
+}
+
 async fn octocrab_repos_create(
     octocrab_client: &octocrab::Octocrab,
+    org_opt: &Option<&str>,
+    is_public: &bool,
+    name: &str,
+    description: &str,
+    options: &OctocrabReposCreateOptions,
+) -> GhRsResult<octocrab::models::Repository> {
+    let mut body = serde_json::json!({
+        "private": !*is_public,
+        "name": name,
+        "description": description,
+        "auto_init": false,
+    });
+    if let Ok(serde_json::Value::Object(options_map)) = serde_json::to_value(options) {
+        body.as_object_mut().expect("is object").extend(options_map);
+    }
+    if options.visibility.is_some() {
+        body.as_object_mut().expect("is object").remove("private");
+    }
+    let create_path = match org_opt {
+        Some(org) => format!("/orgs/{org}/repos"),
+        None => "/user/repos".to_string(),
+    };
+    let gh_repo: octocrab::models::Repository = octocrab_client.post(
+        create_path,
+        Some(&body),
+    ).await?;
+    Ok(gh_repo)
+}
+
+async fn octocrab_repos_generate(
+    octocrab_client: &octocrab::Octocrab,
+    template_owner: &str,
+    template_repo_name: &str,
+    owner: &str,
     is_public: &bool,
     name: &str,
     description: &str,
+    include_all_branches: &bool,
 ) -> GhRsResult<octocrab::models::Repository> {
     let gh_repo: octocrab::models::Repository = octocrab_client.post(
-        "/user/repos",
+        format!("/repos/{template_owner}/{template_repo_name}/generate"),
         Some(&serde_json::json!({
-            "private": !*is_public,
+            "owner": owner,
             "name": name,
             "description": description,
-            "auto_init": false,
+            "private": !*is_public,
+            "include_all_branches": *include_all_branches,
         })),
     ).await?;
     Ok(gh_repo)
 }
 
-async fn git2_add_and_commit<'repo, 'sig>(
-    repo: &'repo git2::Repository,
-    signature: &git2::Signature<'sig>,
-    file_paths: &[&path::PathBuf],
-    message: &str,
-) -> GhRsResult<git2::Commit<'repo>> {
-    let mut repo_index = repo.index()?;
-    let repo_path = repo.path();
-    for path in file_paths {
-        let path_abs = match path.canonicalize().await {
-            Ok(p) => p,
-            Err(e) => return_cmd_err!(
-                "Cannot canonicalize path {path_display}, error: {e}",
-                path_display = path_display(path)
-            )
-        };
-        let path_abs_sync = path_to_sync(&path_abs);
-        let path_rel = match path_rel_sync(repo_path, path_abs_sync.as_path()) {
-            Ok(p) => p,
-            Err(e) => return_cmd_err!(
-                "Cannot find file to repo relative path (repo: {repo_path_display}, file: {file_path_display}), error: {e}",
-                repo_path_display = path_display_sync(&(repo_path.to_path_buf())),
-                file_path_display = path_display_sync(&path_abs_sync)
-            )
+async fn octocrab_repos_update(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    fields: &serde_json::Value,
+) -> GhRsResult<octocrab::models::Repository> {
+    let gh_repo: octocrab::models::Repository = octocrab_client.patch(
+        format!("/repos/{owner}/{repo_name}"),
+        Some(fields),
+    ).await?;
+    Ok(gh_repo)
+}
 
-        };
-        let path_rel = path_rel.strip_prefix("../").unwrap_or(&path_rel);
-        repo_index.add_path(path_rel)?;
-    }
-    let tree_id = repo_index.write_tree()?;
-    let tree = repo.find_tree(tree_id)?;
-    let mut parents = vec![];
-    if let Ok(head) = repo.head() {
-        if let Ok(commit) = head.peel_to_commit() {
-            parents.push(commit);
+// Needs "delete_repo" scope of access token
+async fn octocrab_repos_delete(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+) -> GhRsResult<()> {
+    let response = octocrab_client._delete(
+        format!("/repos/{owner}/{repo_name}"),
+        None::<&()>,
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+// Accepted with 202, completes in background
+async fn octocrab_repos_transfer(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    new_owner: &str,
+    new_repo_name: &str,
+) -> GhRsResult<()> {
+    let _: serde_json::Value = octocrab_client.post(
+        format!("/repos/{owner}/{repo_name}/transfer"),
+        Some(&serde_json::json!({
+            "new_owner": new_owner,
+            "new_name": new_repo_name,
+        })),
+    ).await?;
+    Ok(())
+}
+
+async fn octocrab_repos_update_security(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    options: &OctocrabReposCreateOptions,
+) -> GhRsResult<()> {
+    let mut security_and_analysis = serde_json::Map::new();
+    let features = [
+        ("secret_scanning", options.secret_scanning),
+        ("secret_scanning_push_protection", options.secret_scanning_push_protection),
+    ];
+    for (feature, enabled_opt) in features {
+        if let Some(enabled) = enabled_opt {
+            let status = match enabled {
+                true => "enabled",
+                false => "disabled",
+            };
+            security_and_analysis.insert(feature.to_string(), serde_json::json!({ "status": status }));
         }
     }
-    let pref: Vec<&git2::Commit> = parents.iter().collect();
-    let commit_id = repo.commit(
-        Some("HEAD"), 
-        signature, 
-        signature, 
-        message,
-        &tree,
-        &pref,
-    )?;
-    let commit = repo.find_commit(commit_id)?;
-    Ok(commit)
+    if security_and_analysis.is_empty() {
+        return Ok(());
+    }
+    let _ = octocrab_repos_update(
+        octocrab_client,
+        owner,
+        repo_name,
+        &serde_json::json!({ "security_and_analysis": security_and_analysis }),
+    ).await?;
+    Ok(())
 }
 
-async fn git2_add_all_and_commit<'repo, 'sig>(
-    repo: &'repo git2::Repository,
-    signature: &git2::Signature<'sig>,
-    pathspecs: impl IntoIterator<Item = impl git2::IntoCString>,
-    message: &str,
-) -> GhRsResult<git2::Commit<'repo>> {
-    let mut repo_index = repo.index()?;
-    repo_index.add_all(pathspecs, git2::IndexAddOption::CHECK_PATHSPEC, None)?;
-    let tree_id = repo_index.write_tree()?;
-    let tree = repo.find_tree(tree_id)?;
-    let mut parents = vec![];
-    if let Ok(head) = repo.head() {
-        if let Ok(commit) = head.peel_to_commit() {
-            parents.push(commit);
+// For repos not created by octocrab_repos_create (generated or already existing)
+async fn octocrab_repos_update_options(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    options: &OctocrabReposCreateOptions,
+) -> GhRsResult<()> {
+    if let Ok(serde_json::Value::Object(options_map)) = serde_json::to_value(options) {
+        if !options_map.is_empty() {
+            let _ = octocrab_repos_update(octocrab_client, owner, repo_name, &serde_json::Value::Object(options_map)).await?;
         }
     }
-    let pref: Vec<&git2::Commit> = parents.iter().collect();
-    let commit_id = repo.commit(
-        Some("HEAD"), 
-        signature, 
-        signature, 
-        message,
-        &tree,
-        &pref,
-    )?;
-    let commit = repo.find_commit(commit_id)?;
-    Ok(commit)
+    octocrab_repos_update_security(octocrab_client, owner, repo_name, options).await
 }
 
-fn git2_set_branch_upstream(
-    repo: &git2::Repository,
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelRuleset {
+    pub id: u64,
+    pub name: String,
+}
+
+async fn octocrab_rulesets_list(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+) -> GhRsResult<Vec<OctocrabModelRuleset>> {
+    let rulesets: Vec<OctocrabModelRuleset> = octocrab_client.get(
+        format!("/repos/{owner}/{repo_name}/rulesets"),
+        None::<&()>,
+    ).await?;
+    Ok(rulesets)
+}
+
+// Full ruleset with conditions & rules, listing returns only summaries
+async fn octocrab_rulesets_get(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    ruleset_id: u64,
+) -> GhRsResult<serde_json::Value> {
+    let ruleset: serde_json::Value = octocrab_client.get(
+        format!("/repos/{owner}/{repo_name}/rulesets/{ruleset_id}"),
+        None::<&()>,
+    ).await?;
+    Ok(ruleset)
+}
+
+// Creates ruleset or replaces the one with the same name
+async fn octocrab_rulesets_upsert(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    ruleset: &serde_json::Value,
+) -> GhRsResult<OctocrabModelRuleset> {
+    let rulesets = octocrab_rulesets_list(octocrab_client, owner, repo_name).await?;
+    let existing_opt = rulesets.iter().find(|r| Some(r.name.as_str()) == ruleset["name"].as_str());
+    let ruleset: OctocrabModelRuleset = match existing_opt {
+        Some(existing) => octocrab_client.put(
+            format!("/repos/{owner}/{repo_name}/rulesets/{id}", id = existing.id),
+            Some(ruleset),
+        ).await?,
+        None => octocrab_client.post(
+            format!("/repos/{owner}/{repo_name}/rulesets"),
+            Some(ruleset),
+        ).await?,
+    };
+    Ok(ruleset)
+}
+
+async fn octocrab_branch_protection_update(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
     branch_name: &str,
-    remote_name: &str,
+    protection: &serde_json::Value,
 ) -> GhRsResult<()> {
-    let mut branch = repo.find_branch(
-        branch_name, 
-        git2::BranchType::Local
-    )?;
-    branch.set_upstream(Some(format!("{remote_name}/{branch_name}").as_str()))?;
+    let _: serde_json::Value = octocrab_client.put(
+        format!("/repos/{owner}/{repo_name}/branches/{branch_name}/protection", branch_name = url_path_segment(branch_name)),
+        Some(protection),
+    ).await?;
     Ok(())
 }
 
-fn git2_credentials(
-    url: &str,
-    username_from_url: Option<&str>,
-    allowed: git2::CredentialType,
+async fn octocrab_teams_add_repo(
+    octocrab_client: &octocrab::Octocrab,
+    org: &str,
+    team_slug: &str,
+    repo_name: &str,
+    permission: &str,
+) -> GhRsResult<()> {
+    let response = octocrab_client._put(
+        format!("/orgs/{org}/teams/{team_slug}/repos/{org}/{repo_name}"),
+        Some(&serde_json::json!({ "permission": permission })),
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
 
-    username: &str,
-    password: &str,
-    ssh_private_key: &ssh_key::PrivateKey
-) -> Result<git2::Cred, git2::Error> {
-    if allowed.contains(git2::CredentialType::SSH_MEMORY) {
-        git2::Cred::ssh_key_from_memory(            
-            username_from_url.expect("must be some"),
-            Some(ssh_private_key.public_key().to_openssh().expect("must be able to convert to openssh format").deref()), 
-            ssh_private_key.to_openssh(ssh_key::LineEnding::default()).expect("must be able to convert to openssh format").deref(), 
-            None
-        )
-    } else if allowed.contains(git2::CredentialType::USERNAME) {
-        git2::Cred::username(username)
-    } else if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
-        git2::Cred::userpass_plaintext(username, password)
-    } else {
-        Err(git2::Error::from_str(format!("no callbacks for type: {allowed:?}").as_str()))
-    }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelGitignoreTemplate {
+    pub name: String,
+    pub source: String,
 }
 
+async fn octocrab_gitignore_templates(
+    octocrab_client: &octocrab::Octocrab,
+) -> GhRsResult<Vec<String>> {
+    let names: Vec<String> = octocrab_client.get("/gitignore/templates", None::<&()>).await?;
+    Ok(names)
+}
 
-fn git2_remote_delete(
-    repo: &git2::Repository,
-    remote_name: &str,
-) -> Result<(), git2::Error> {
-    // fixes error when having multivar refspecs on remote in config
-    
-    fn remove_branch_config_related_entries(
-        repo: &git2::Repository,
-        remote_name: &str,
-    ) -> Result<(), git2::Error> {
-        let config = repo.config()?;
-        // Some(r"branch\..+\.remote")
-        let mut entries = config.entries(None)?;
-        let mut entries_to_remove = HashSet::new();
-        while let Some(entry) = entries.next() {
-            let entry = entry?;
-            let entry_value = match entry.value() {
-                None => continue,
-                Some(value) => {
-                    if value != remote_name { continue; }
-                    value
-                }
-            };
-            let entry_name = match entry.name() {
-                None => continue,
-                Some(name) => name,
-            };
-            let branch = regex_groups(&re(r"branch\.(.+)\.remote"), entry_name)[1].as_str();
-            entries_to_remove.insert(format!("branch.{branch}.merge"));
-            entries_to_remove.insert(format!("branch.{branch}.remote"));
-        }
-        let mut config = repo.config()?;
-        for entry in entries_to_remove {
-            config.remove_multivar(entry.as_str(), r".*")?;
-        }
-        Ok(())
-    }
+async fn octocrab_gitignore_template(
+    octocrab_client: &octocrab::Octocrab,
+    name: &str,
+) -> GhRsResult<OctocrabModelGitignoreTemplate> {
+    let template: OctocrabModelGitignoreTemplate = octocrab_client.get(
+        format!("/gitignore/templates/{name}", name = url_path_segment(name)),
+        None::<&()>,
+    ).await?;
+    Ok(template)
+}
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelLicense {
+    pub key: String,
+    pub name: String,
+    pub spdx_id: Option<String>,
+    pub body: Option<String>,
+}
 
-    fn remove_refs(
-        repo: &git2::Repository,
-        spec: &git2::Refspec,
-    ) -> Result<(), git2::Error> {
-        let mut references_to_remove = HashSet::new();
+async fn octocrab_licenses(
+    octocrab_client: &octocrab::Octocrab,
+) -> GhRsResult<Vec<OctocrabModelLicense>> {
+    let licenses: Vec<OctocrabModelLicense> = octocrab_client.get("/licenses", None::<&()>).await?;
+    Ok(licenses)
+}
+
+async fn octocrab_license(
+    octocrab_client: &octocrab::Octocrab,
+    key: &str,
+) -> GhRsResult<OctocrabModelLicense> {
+    let license: OctocrabModelLicense = octocrab_client.get(
+        format!("/licenses/{key}", key = url_path_segment(key)),
+        None::<&()>,
+    ).await?;
+    Ok(license)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelTopics {
+    pub names: Vec<String>,
+}
+
+async fn octocrab_repos_replace_topics(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    topics: &[String],
+) -> GhRsResult<OctocrabModelTopics> {
+    let octo_topics: OctocrabModelTopics = octocrab_client.put(
+        format!("/repos/{owner}/{repo_name}/topics"),
+        Some(&serde_json::json!({
+            "names": topics,
+        })),
+    ).await?;
+    Ok(octo_topics)
+}
+
+async fn octocrab_get_all_pages<T: serde::de::DeserializeOwned>(
+    octocrab_client: &octocrab::Octocrab,
+    route: &str,
+    parameters: &[(&str, &str)],
+) -> GhRsResult<Vec<T>> {
+    let mut parameters = parameters.to_vec();
+    parameters.push(("per_page", "100"));
+    let page: octocrab::Page<T> = octocrab_client.get(route, Some(&parameters)).await?;
+    Ok(octocrab_client.all_pages(page).await?)
+}
+
+async fn octocrab_repos_is_empty(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+) -> GhRsResult<bool> {
+    let branches = octocrab_client.repos(owner, repo_name).list_branches()
+        .per_page(1)
+        .send().await?;
+    Ok(branches.items.is_empty())
+}
+
+fn url_path_segment(segment: &str) -> String {
+    let mut url = url::Url::parse("https://github.com").expect("valid url");
+    url.path_segments_mut().expect("can be a base").push(segment);
+    url.path().trim_start_matches('/').to_string()
+}
+
+async fn octocrab_labels_copy(
+    octocrab_client: &octocrab::Octocrab,
+    from_owner: &str,
+    from_repo_name: &str,
+    to_owner: &str,
+    to_repo_name: &str,
+) -> GhRsResult<usize> {
+    let from_labels: Vec<octocrab::models::Label> = octocrab_get_all_pages(
+        octocrab_client,
+        &format!("/repos/{from_owner}/{from_repo_name}/labels"),
+        &[],
+    ).await?;
+    let to_labels: Vec<octocrab::models::Label> = octocrab_get_all_pages(
+        octocrab_client,
+        &format!("/repos/{to_owner}/{to_repo_name}/labels"),
+        &[],
+    ).await?;
+
+    // new repos come with GitHub default labels, replace them with the external ones
+    for to_label in &to_labels {
+        if from_labels.iter().any(|l| l.name.to_lowercase() == to_label.name.to_lowercase()) { continue; }
+        let response = octocrab_client._delete(
+            format!("/repos/{to_owner}/{to_repo_name}/labels/{name}", name = url_path_segment(&to_label.name)),
+            None::<&()>,
+        ).await?;
+        octocrab::map_github_error(response).await?;
+    }
+    for from_label in &from_labels {
+        let fields = serde_json::json!({
+            "name": from_label.name,
+            "color": from_label.color,
+            "description": from_label.description.clone().unwrap_or_default(),
+        });
+        let to_label_opt = to_labels.iter().find(|l| l.name.to_lowercase() == from_label.name.to_lowercase());
+        let _: octocrab::models::Label = match to_label_opt {
+            Some(to_label) => octocrab_client.patch(
+                format!("/repos/{to_owner}/{to_repo_name}/labels/{name}", name = url_path_segment(&to_label.name)),
+                Some(&fields),
+            ).await?,
+            None => octocrab_client.post(
+                format!("/repos/{to_owner}/{to_repo_name}/labels"),
+                Some(&fields),
+            ).await?,
+        };
+    }
+    Ok(from_labels.len())
+}
+
+async fn octocrab_milestones_copy(
+    octocrab_client: &octocrab::Octocrab,
+    from_owner: &str,
+    from_repo_name: &str,
+    to_owner: &str,
+    to_repo_name: &str,
+) -> GhRsResult<usize> {
+    let mut from_milestones: Vec<octocrab::models::Milestone> = octocrab_get_all_pages(
+        octocrab_client,
+        &format!("/repos/{from_owner}/{from_repo_name}/milestones"),
+        &[("state", "all")],
+    ).await?;
+    // keep numbering as close to the external repo as possible
+    from_milestones.sort_by_key(|m| m.number);
+    for from_milestone in &from_milestones {
+        let mut fields = serde_json::json!({
+            "title": from_milestone.title,
+            "state": from_milestone.state.clone().unwrap_or("open".to_string()),
+        });
+        if let Some(description) = &from_milestone.description {
+            fields["description"] = serde_json::json!(description);
+        }
+        if let Some(due_on) = &from_milestone.due_on {
+            fields["due_on"] = serde_json::json!(due_on);
+        }
+        let _: octocrab::models::Milestone = octocrab_client.post(
+            format!("/repos/{to_owner}/{to_repo_name}/milestones"),
+            Some(&fields),
+        ).await?;
+    }
+    Ok(from_milestones.len())
+}
+
+async fn octocrab_labels_list(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+) -> GhRsResult<Vec<octocrab::models::Label>> {
+    octocrab_get_all_pages(octocrab_client, &format!("/repos/{owner}/{repo_name}/labels"), &[]).await
+}
+
+async fn octocrab_labels_create(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    fields: &serde_json::Value,
+) -> GhRsResult<octocrab::models::Label> {
+    let label: octocrab::models::Label = octocrab_client.post(
+        format!("/repos/{owner}/{repo_name}/labels"),
+        Some(fields),
+    ).await?;
+    Ok(label)
+}
+
+async fn octocrab_labels_update(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    label_name: &str,
+    fields: &serde_json::Value,
+) -> GhRsResult<octocrab::models::Label> {
+    let label: octocrab::models::Label = octocrab_client.patch(
+        format!("/repos/{owner}/{repo_name}/labels/{name}", name = url_path_segment(label_name)),
+        Some(fields),
+    ).await?;
+    Ok(label)
+}
+
+async fn octocrab_labels_delete(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    label_name: &str,
+) -> GhRsResult<()> {
+    let response = octocrab_client._delete(
+        format!("/repos/{owner}/{repo_name}/labels/{name}", name = url_path_segment(label_name)),
+        None::<&()>,
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelLogin {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelCollaborator {
+    pub login: String,
+    // "admin", "maintain", "write", "triage", "read" or custom role
+    pub role_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelInvitationRepo {
+    pub full_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelInvitation {
+    pub id: u64,
+    pub repository: Option<OctocrabModelInvitationRepo>,
+    pub invitee: Option<OctocrabModelLogin>,
+    pub inviter: Option<OctocrabModelLogin>,
+    // "read", "write", "triage", "maintain" or "admin"
+    pub permissions: String,
+}
+
+// affiliation "direct" skips org members with access through teams or base permissions, "all" lists them
+async fn octocrab_collaborators_list(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    affiliation: &str,
+) -> GhRsResult<Vec<OctocrabModelCollaborator>> {
+    octocrab_get_all_pages(
+        octocrab_client,
+        &format!("/repos/{owner}/{repo_name}/collaborators"),
+        &[("affiliation", affiliation)],
+    ).await
+}
+
+// Pending invitations of repo
+async fn octocrab_repo_invitations_list(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+) -> GhRsResult<Vec<OctocrabModelInvitation>> {
+    octocrab_get_all_pages(octocrab_client, &format!("/repos/{owner}/{repo_name}/invitations"), &[]).await
+}
+
+// Invites new collaborator or changes permission of existing one, true when invitation is sent
+async fn octocrab_collaborators_put(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    username: &str,
+    permission: &str,
+) -> GhRsResult<bool> {
+    let response = octocrab_client._put(
+        format!("/repos/{owner}/{repo_name}/collaborators/{username}"),
+        Some(&serde_json::json!({ "permission": permission })),
+    ).await?;
+    let response = octocrab::map_github_error(response).await?;
+    Ok(response.status().as_u16() == 201)
+}
+
+async fn octocrab_collaborators_remove(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    username: &str,
+) -> GhRsResult<()> {
+    let response = octocrab_client._delete(
+        format!("/repos/{owner}/{repo_name}/collaborators/{username}"),
+        None::<&()>,
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+async fn octocrab_repo_invitations_delete(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    invitation_id: u64,
+) -> GhRsResult<()> {
+    let response = octocrab_client._delete(
+        format!("/repos/{owner}/{repo_name}/invitations/{invitation_id}"),
+        None::<&()>,
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+// Pending invitations of authenticated user
+async fn octocrab_user_invitations_list(
+    octocrab_client: &octocrab::Octocrab,
+) -> GhRsResult<Vec<OctocrabModelInvitation>> {
+    octocrab_get_all_pages(octocrab_client, "/user/repository_invitations", &[]).await
+}
+
+async fn octocrab_user_invitations_accept(
+    octocrab_client: &octocrab::Octocrab,
+    invitation_id: u64,
+) -> GhRsResult<()> {
+    let response = octocrab_client._patch(
+        format!("/user/repository_invitations/{invitation_id}"),
+        None::<&()>,
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+async fn octocrab_user_invitations_decline(
+    octocrab_client: &octocrab::Octocrab,
+    invitation_id: u64,
+) -> GhRsResult<()> {
+    let response = octocrab_client._delete(
+        format!("/user/repository_invitations/{invitation_id}"),
+        None::<&()>,
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+// Repos of user or org, archived ones included
+async fn octocrab_owner_repos(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    username: &str,
+) -> GhRsResult<Vec<octocrab::models::Repository>> {
+    // private repos of the authenticated user are only listed by /user/repos
+    if owner.eq_ignore_ascii_case(username) {
+        return octocrab_get_all_pages(octocrab_client, "/user/repos", &[("affiliation", "owner")]).await;
+    }
+    match octocrab_get_all_pages(octocrab_client, &format!("/orgs/{owner}/repos"), &[("type", "all")]).await {
+        Ok(repos) => Ok(repos),
+        Err(_) => octocrab_get_all_pages(octocrab_client, &format!("/users/{owner}/repos"), &[("type", "owner")]).await,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelActionsPublicKey {
+    pub key_id: String,
+    pub key: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OctocrabModelActionsEntry {
+    pub name: String,
+    // variables only, secrets are never returned
+    pub value: Option<String>,
+    pub updated_at: Option<String>,
+    // org level only
+    pub visibility: Option<String>,
+}
+
+// Key to seal secrets of repo, environment or org with
+async fn octocrab_actions_public_key(
+    octocrab_client: &octocrab::Octocrab,
+    scope: &actions::ActionsScope,
+) -> GhRsResult<OctocrabModelActionsPublicKey> {
+    let route = format!("{path}/public-key", path = scope.path("secrets"));
+    Ok(octocrab_client.get(route, None::<&()>).await?)
+}
+
+// Lists are wrapped as { total_count, secrets } or { total_count, variables }, not supported by octocrab::Page
+async fn octocrab_actions_list(
+    octocrab_client: &octocrab::Octocrab,
+    scope: &actions::ActionsScope,
+    kind: &str,
+) -> GhRsResult<Vec<OctocrabModelActionsEntry>> {
+    let route = scope.path(kind);
+    let mut entries = vec![];
+    for page in 1.. {
+        let page_raw = page.to_string();
+        let response: serde_json::Value = octocrab_client.get(
+            &route,
+            Some(&[("per_page", "100"), ("page", page_raw.as_str())]),
+        ).await?;
+        let page_entries: Vec<OctocrabModelActionsEntry> = match serde_json::from_value(response[kind].clone()) {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Unexpected response of {route}, error: {e}"),
+        };
+        let total_count = response["total_count"].as_u64().unwrap_or(0) as usize;
+        let is_last = page_entries.is_empty();
+        entries.extend(page_entries);
+        if is_last || entries.len() >= total_count {
+            break;
+        }
+    }
+    Ok(entries)
+}
+
+async fn octocrab_actions_secret_put(
+    octocrab_client: &octocrab::Octocrab,
+    scope: &actions::ActionsScope,
+    name: &str,
+    encrypted_value: &str,
+    key_id: &str,
+) -> GhRsResult<()> {
+    let mut body = serde_json::json!({ "encrypted_value": encrypted_value, "key_id": key_id });
+    scope.extend_body(&mut body);
+    let response = octocrab_client._put(
+        format!("{path}/{name}", path = scope.path("secrets")),
+        Some(&body),
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+// Existing variables are updated, others created
+async fn octocrab_actions_variable_put(
+    octocrab_client: &octocrab::Octocrab,
+    scope: &actions::ActionsScope,
+    name: &str,
+    value: &str,
+    exists: bool,
+) -> GhRsResult<()> {
+    let mut body = serde_json::json!({ "name": name, "value": value });
+    scope.extend_body(&mut body);
+    let path = scope.path("variables");
+    let response = match exists {
+        true => octocrab_client._patch(format!("{path}/{name}"), Some(&body)).await?,
+        false => octocrab_client._post(path, Some(&body)).await?,
+    };
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+async fn octocrab_actions_delete(
+    octocrab_client: &octocrab::Octocrab,
+    scope: &actions::ActionsScope,
+    kind: &str,
+    name: &str,
+) -> GhRsResult<()> {
+    let response = octocrab_client._delete(
+        format!("{path}/{name}", path = scope.path(kind)),
+        None::<&()>,
+    ).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
+fn reqwest_client(token: &str) -> GhRsResult<reqwest::Client> {
+    let mut auth_value = match reqwest::header::HeaderValue::from_str(format!("Bearer {token}").as_str()) {
+        Ok(v) => v,
+        Err(e) => return_cmd_err!("Failed to use access token as header, error: {e}"),
+    };
+    auth_value.set_sensitive(true);
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(reqwest::header::AUTHORIZATION, auth_value);
+    let http_client = reqwest::Client::builder()
+        .user_agent(gh_rs_github_repo_name)
+        .default_headers(headers)
+        .build()?;
+    Ok(http_client)
+}
+
+async fn reqwest_release_asset_copy(
+    http_client: &reqwest::Client,
+    asset: &octocrab::models::repos::Asset,
+    upload_url_template: &str,
+) -> GhRsResult<()> {
+    // redirects to storage, authorization header is dropped by reqwest on other hosts
+    let content = http_client.get(asset.url.clone())
+        .header(reqwest::header::ACCEPT, "application/octet-stream")
+        .send().await?
+        .error_for_status()?
+        .bytes().await?;
+    let upload_url_base = upload_url_template.split_once('{').map(|(u, _)| u).unwrap_or(upload_url_template);
+    let mut upload_params = vec![("name", asset.name.as_str())];
+    if let Some(label) = &asset.label {
+        upload_params.push(("label", label.as_str()));
+    }
+    let upload_url = match url::Url::parse_with_params(upload_url_base, &upload_params) {
+        Ok(u) => u,
+        Err(e) => return_cmd_err!("Invalid release upload url {upload_url_base}, error: {e}"),
+    };
+    http_client.post(upload_url)
+        .header(reqwest::header::CONTENT_TYPE, asset.content_type.as_str())
+        .body(content)
+        .send().await?
+        .error_for_status()?;
+    Ok(())
+}
+
+async fn octocrab_releases_copy(
+    octocrab_client: &octocrab::Octocrab,
+    http_client: &reqwest::Client,
+    from_owner: &str,
+    from_repo_name: &str,
+    to_owner: &str,
+    to_repo_name: &str,
+) -> GhRsResult<usize> {
+    let mut from_releases: Vec<octocrab::models::repos::Release> = octocrab_get_all_pages(
+        octocrab_client,
+        &format!("/repos/{from_owner}/{from_repo_name}/releases"),
+        &[],
+    ).await?;
+    // API lists newest first, create oldest first so "latest" stays the same
+    from_releases.sort_by_key(|r| r.created_at);
+    for from_release in &from_releases {
+        let to_release: octocrab::models::repos::Release = octocrab_client.post(
+            format!("/repos/{to_owner}/{to_repo_name}/releases"),
+            Some(&serde_json::json!({
+                "tag_name": from_release.tag_name,
+                "target_commitish": from_release.target_commitish,
+                "name": from_release.name.clone().unwrap_or_default(),
+                "body": from_release.body.clone().unwrap_or_default(),
+                "draft": from_release.draft,
+                "prerelease": from_release.prerelease,
+            })),
+        ).await?;
+        for asset in &from_release.assets {
+            reqwest_release_asset_copy(http_client, asset, &to_release.upload_url).await?;
+        }
+    }
+    Ok(from_releases.len())
+}
+
+async fn git2_add_and_commit<'repo, 'sig>(
+    repo: &'repo git2::Repository,
+    signature: &git2::Signature<'sig>,
+    file_paths: &[&path::PathBuf],
+    message: &str,
+) -> GhRsResult<git2::Commit<'repo>> {
+    let mut repo_index = repo.index()?;
+    let repo_path = repo.path();
+    for path in file_paths {
+        let path_abs = match path.canonicalize().await {
+            Ok(p) => p,
+            Err(e) => return_cmd_err!(
+                "Cannot canonicalize path {path_display}, error: {e}",
+                path_display = path_display(path)
+            )
+        };
+        let path_abs_sync = path_to_sync(&path_abs);
+        let path_rel = match path_rel_sync(repo_path, path_abs_sync.as_path()) {
+            Ok(p) => p,
+            Err(e) => return_cmd_err!(
+                "Cannot find file to repo relative path (repo: {repo_path_display}, file: {file_path_display}), error: {e}",
+                repo_path_display = path_display_sync(&(repo_path.to_path_buf())),
+                file_path_display = path_display_sync(&path_abs_sync)
+            )
+
+        };
+        let path_rel = path_rel.strip_prefix("../").unwrap_or(&path_rel);
+        repo_index.add_path(path_rel)?;
+    }
+    lfs::clean(repo, &mut repo_index)?;
+    repo_index.write()?;
+    let tree_id = repo_index.write_tree()?;
+    let tree = repo.find_tree(tree_id)?;
+    let mut parents = vec![];
+    if let Ok(head) = repo.head() {
+        if let Ok(commit) = head.peel_to_commit() {
+            parents.push(commit);
+        }
+    }
+    let pref: Vec<&git2::Commit> = parents.iter().collect();
+    let commit_id = repo.commit(
+        Some("HEAD"), 
+        signature, 
+        signature, 
+        message,
+        &tree,
+        &pref,
+    )?;
+    let commit = repo.find_commit(commit_id)?;
+    Ok(commit)
+}
+
+async fn git2_add_all_and_commit<'repo, 'sig>(
+    repo: &'repo git2::Repository,
+    signature: &git2::Signature<'sig>,
+    pathspecs: impl IntoIterator<Item = impl git2::IntoCString>,
+    message: &str,
+) -> GhRsResult<git2::Commit<'repo>> {
+    let mut repo_index = repo.index()?;
+    repo_index.add_all(pathspecs, git2::IndexAddOption::CHECK_PATHSPEC, None)?;
+    lfs::clean(repo, &mut repo_index)?;
+    repo_index.write()?;
+    let tree_id = repo_index.write_tree()?;
+    let tree = repo.find_tree(tree_id)?;
+    let mut parents = vec![];
+    if let Ok(head) = repo.head() {
+        if let Ok(commit) = head.peel_to_commit() {
+            parents.push(commit);
+        }
+    }
+    let pref: Vec<&git2::Commit> = parents.iter().collect();
+    let commit_id = repo.commit(
+        Some("HEAD"), 
+        signature, 
+        signature, 
+        message,
+        &tree,
+        &pref,
+    )?;
+    let commit = repo.find_commit(commit_id)?;
+    Ok(commit)
+}
+
+// Commits paths relative to working tree on top of HEAD tree, anything else already staged stays out of the commit
+fn git2_commit_paths<'repo>(
+    repo: &'repo git2::Repository,
+    signature: &git2::Signature<'_>,
+    paths: &[String],
+    message: &str,
+) -> GhRsResult<git2::Commit<'repo>> {
+    let head_commit_opt = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    // staged as usual (LFS pointers included), then only their entries are copied onto HEAD tree
+    let mut repo_index = repo.index()?;
+    for path in paths {
+        repo_index.add_path(std::path::Path::new(path))?;
+    }
+    lfs::clean(repo, &mut repo_index)?;
+    repo_index.write()?;
+    let mut commit_index = git2::Index::new()?;
+    if let Some(head_commit) = &head_commit_opt {
+        commit_index.read_tree(&head_commit.tree()?)?;
+    }
+    for path in paths {
+        match repo_index.get_path(std::path::Path::new(path), 0) {
+            Some(entry) => commit_index.add(&entry)?,
+            None => return_cmd_err!("Failed to stage {path}"),
+        }
+    }
+    let tree = repo.find_tree(commit_index.write_tree_to(repo)?)?;
+    let parents: Vec<&git2::Commit> = head_commit_opt.iter().collect();
+    let commit_id = repo.commit(Some("HEAD"), signature, signature, message, &tree, &parents)?;
+    Ok(repo.find_commit(commit_id)?)
+}
+
+fn git2_push_ref_names(repo: &git2::Repository) -> GhRsResult<Vec<String>> {
+    let mut ref_names = vec![];
+    for reference in repo.references()?.flatten() {
+        let name = match reference.name() {
+            Some(n) => n,
+            None => continue,
+        };
+        if name.ends_with("/HEAD") || reference.peel_to_commit().is_err() { continue; }
+        ref_names.push(name.to_string());
+    }
+    Ok(ref_names)
+}
+
+fn git2_large_blobs_in_tree(
+    repo: &git2::Repository,
+    odb: &git2::Odb,
+    tree: &git2::Tree,
+    tree_path: &str,
+    size_min: usize,
+    seen_ids: &mut HashSet<git2::Oid>,
+    large_blobs: &mut Vec<(String, usize)>,
+    objects_size: &mut usize,
+) -> GhRsResult<()> {
+    for entry in tree.iter() {
+        if !seen_ids.insert(entry.id()) { continue; }
+        let path = format!("{tree_path}{name}", name = entry.name().unwrap_or(""));
+        match entry.kind() {
+            Some(git2::ObjectType::Tree) => {
+                *objects_size += odb.read_header(entry.id())?.0;
+                let subtree = repo.find_tree(entry.id())?;
+                git2_large_blobs_in_tree(repo, odb, &subtree, &format!("{path}/"), size_min, seen_ids, large_blobs, objects_size)?;
+            },
+            Some(git2::ObjectType::Blob) => {
+                let (size, _) = odb.read_header(entry.id())?;
+                *objects_size += size;
+                if size >= size_min {
+                    large_blobs.push((path, size));
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+// Blobs at least size_min bytes in history of refs, with the first path seen,
+// & total size of objects to push, uncompressed, read from object headers only
+fn git2_history_size(
+    repo: &git2::Repository,
+    ref_names: &[&str],
+    size_min: usize,
+) -> GhRsResult<(Vec<(String, usize)>, usize)> {
+    let odb = repo.odb()?;
+    let mut revwalk = repo.revwalk()?;
+    for ref_name in ref_names {
+        revwalk.push(repo.find_reference(ref_name)?.peel_to_commit()?.id())?;
+    }
+    let mut seen_ids = HashSet::new();
+    let mut large_blobs = vec![];
+    let mut objects_size = 0;
+    for commit_id in revwalk {
+        let commit_id = commit_id?;
+        objects_size += odb.read_header(commit_id)?.0;
+        let tree = repo.find_commit(commit_id)?.tree()?;
+        if !seen_ids.insert(tree.id()) { continue; }
+        objects_size += odb.read_header(tree.id())?.0;
+        git2_large_blobs_in_tree(repo, &odb, &tree, "", size_min, &mut seen_ids, &mut large_blobs, &mut objects_size)?;
+    }
+    large_blobs.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    Ok((large_blobs, objects_size))
+}
+
+fn git2_is_dirty(repo: &git2::Repository) -> GhRsResult<bool> {
+    let mut status_options = git2::StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false)
+        .exclude_submodules(true);
+    let statuses = repo.statuses(Some(&mut status_options))?;
+    Ok(!statuses.is_empty())
+}
+
+fn git2_set_branch_upstream(
+    repo: &git2::Repository,
+    branch_name: &str,
+    remote_name: &str,
+) -> GhRsResult<()> {
+    let mut branch = repo.find_branch(
+        branch_name, 
+        git2::BranchType::Local
+    )?;
+    branch.set_upstream(Some(format!("{remote_name}/{branch_name}").as_str()))?;
+    Ok(())
+}
+
+fn git2_credentials(
+    url: &str,
+    username_from_url: Option<&str>,
+    allowed: git2::CredentialType,
+
+    username: &str,
+    password: &str,
+    ssh_private_key: &ssh_key::PrivateKey
+) -> Result<git2::Cred, git2::Error> {
+    if allowed.contains(git2::CredentialType::SSH_MEMORY) {
+        git2::Cred::ssh_key_from_memory(            
+            username_from_url.expect("must be some"),
+            Some(ssh_private_key.public_key().to_openssh().expect("must be able to convert to openssh format").deref()), 
+            ssh_private_key.to_openssh(ssh_key::LineEnding::default()).expect("must be able to convert to openssh format").deref(), 
+            None
+        )
+    } else if allowed.contains(git2::CredentialType::USERNAME) {
+        git2::Cred::username(username)
+    } else if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+        git2::Cred::userpass_plaintext(username, password)
+    } else {
+        Err(git2::Error::from_str(format!("no callbacks for type: {allowed:?}").as_str()))
+    }
+}
+
+fn git2_credentials_from_helpers(
+    url: &str,
+    username_from_url: Option<&str>,
+    allowed: git2::CredentialType,
+
+    config: &git2::Config,
+) -> Result<git2::Cred, git2::Error> {
+    if allowed.contains(git2::CredentialType::SSH_KEY) {
+        git2::Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"))
+    } else if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+        git2::Cred::credential_helper(config, url, username_from_url)
+    } else if allowed.contains(git2::CredentialType::USERNAME) {
+        git2::Cred::username(username_from_url.unwrap_or("git"))
+    } else if allowed.contains(git2::CredentialType::DEFAULT) {
+        git2::Cred::default()
+    } else {
+        Err(git2::Error::from_str(format!("no callbacks for type: {allowed:?}").as_str()))
+    }
+}
+
+fn git2_remote_delete(
+    repo: &git2::Repository,
+    remote_name: &str,
+) -> Result<(), git2::Error> {
+    // fixes error when having multivar refspecs on remote in config
+    
+    fn remove_branch_config_related_entries(
+        repo: &git2::Repository,
+        remote_name: &str,
+    ) -> Result<(), git2::Error> {
+        let config = repo.config()?;
+        // Some(r"branch\..+\.remote")
+        let mut entries = config.entries(None)?;
+        let mut entries_to_remove = HashSet::new();
+        while let Some(entry) = entries.next() {
+            let entry = entry?;
+            let entry_value = match entry.value() {
+                None => continue,
+                Some(value) => {
+                    if value != remote_name { continue; }
+                    value
+                }
+            };
+            let entry_name = match entry.name() {
+                None => continue,
+                Some(name) => name,
+            };
+            let branch = regex_groups(&re(r"branch\.(.+)\.remote"), entry_name)[1].as_str();
+            entries_to_remove.insert(format!("branch.{branch}.merge"));
+            entries_to_remove.insert(format!("branch.{branch}.remote"));
+        }
+        let mut config = repo.config()?;
+        for entry in entries_to_remove {
+            config.remove_multivar(entry.as_str(), r".*")?;
+        }
+        Ok(())
+    }
+
+
+    fn remove_refs(
+        repo: &git2::Repository,
+        spec: &git2::Refspec,
+    ) -> Result<(), git2::Error> {
+        let mut references_to_remove = HashSet::new();
         for reference in repo.references()? {
             let reference = reference?;
             let reference_name = match reference.name() {
                 Some(n) => n,
                 None => continue,
             };
-            if !spec.dst_matches(reference_name) { continue; }
-            references_to_remove.insert(reference_name.to_owned());
+            if !spec.dst_matches(reference_name) { continue; }
+            references_to_remove.insert(reference_name.to_owned());
+        }
+        for reference_name in references_to_remove {
+            repo.find_reference(reference_name.as_str())?.delete()?;
+        }
+        Ok(())
+    }
+
+    fn remove_remote_tracking(
+        repo: &git2::Repository,
+        remote_name: &str,
+    ) -> Result<(), git2::Error> {
+        let remote = repo.find_remote(remote_name)?;
+        for refspec in remote.refspecs() {
+            remove_refs(repo, &refspec)?;
+        }
+        Ok(())
+    }
+    
+    fn remove_config_remote_section(
+        repo: &git2::Repository,
+        remote_name: &str,
+    ) -> Result<(), git2::Error> {
+        // this fn fails in libgit2, bcuz of git_config_delete_entry() used, not remove_multivar()
+        let config = repo.config()?;
+        let mut entries = config.entries(Some(format!(r"remote.{remote_name}\..*").as_str()))?;
+        let mut entries_to_remove = HashSet::new();
+        while let Some(entry) = entries.next() {
+            let entry = entry?;
+            let entry_name = entry.name().expect("must be some").to_owned();
+            entries_to_remove.insert(entry_name);
+        }
+        let mut config = repo.config()?;
+        for entry in entries_to_remove {
+            config.remove_multivar(entry.as_str(), r".*")?; // fix is here
+        }
+        Ok(())
+    }
+
+    remove_branch_config_related_entries(repo, remote_name)?;
+    remove_remote_tracking(repo, remote_name)?;
+    
+    // this fn fails in libgit2, bcuz of git_config_delete_entry() used, not remove_multivar()
+    remove_config_remote_section(repo, remote_name)?;
+    
+    Ok(())
+}
+
+fn git2_remote_recreate<'repo>(
+    repo: &'repo git2::Repository,
+    name: &str,
+    url: &str,
+) -> Result<git2::Remote<'repo>, git2::Error> {
+    if let Ok(r) = repo.find_remote(name) {
+        git2_remote_delete(repo, name)?;
+    }
+    repo.remote(name, url)
+}
+
+// Points remotes of owner/repo_name (fetch & push urls) to new_owner/new_repo_name, keeps url format.
+// Returns names of changed remotes
+fn git2_remotes_retarget(
+    repo: &git2::Repository,
+    owner: &str,
+    repo_name: &str,
+    new_owner: &str,
+    new_repo_name: &str,
+) -> GhRsResult<Vec<String>> {
+    let retarget_url = |url: &str| -> Option<String> {
+        let (url_owner, url_repo_name) = resolve_github_git_url(url)?;
+        if !url_owner.eq_ignore_ascii_case(owner) || !url_repo_name.eq_ignore_ascii_case(repo_name) {
+            return None;
+        }
+        let url_path = format!("{url_owner}/{url_repo_name}");
+        let path_start = url.rfind(&url_path)?;
+        Some(format!(
+            "{before}{new_owner}/{new_repo_name}{after}",
+            before = &url[..path_start],
+            after = &url[path_start + url_path.len()..],
+        ))
+    };
+    let mut remote_names = vec![];
+    for remote_name in repo.remotes()?.iter().flatten() {
+        let remote = repo.find_remote(remote_name)?;
+        let mut changed = false;
+        if let Some(new_url) = remote.url().and_then(retarget_url) {
+            repo.remote_set_url(remote_name, &new_url)?;
+            changed = true;
+        }
+        if let Some(new_push_url) = remote.pushurl().and_then(retarget_url) {
+            repo.remote_set_pushurl(remote_name, Some(&new_push_url))?;
+            changed = true;
+        }
+        if changed {
+            remote_names.push(remote_name.to_string());
+        }
+    }
+    Ok(remote_names)
+}
+
+fn git2_generate_refspecs_from_globbed(
+    repo: &git2::Repository,
+    refspecs_globbed: HashSet<String>,
+) -> GhRsResult<HashSet<String>> {
+    let refspecs = HashSet::new();
+    for rg in refspecs_globbed {
+        let mut rg = rg;
+        let is_plus = rg.starts_with("+");
+        if is_plus {
+            rg = rg.strip_prefix("+").unwrap_or(&rg).to_owned();
+        }
+        if !rg.starts_with("refs") {
+            return_cmd_err!("Refspec must start with \"refs\", here: {rg}");
+        }
+        let got_git_path = repo.path();
+        
+    }
+    Ok(refspecs)
+}
+
+fn git2_default_branch_name(
+    repo: &git2::Repository,
+) -> GhRsResult<String> {
+    let head = repo.head()?;
+    let head_name = match head.name() {
+        Some(n) => n, 
+        None => return_cmd_err!("Failed to encode head name as utf8")
+    };
+    let branch_name = head_name.strip_prefix("refs/heads/").unwrap_or(head_name)
+        .to_string();
+    Ok(branch_name)
+}
+
+fn git2_push(
+    repo: &git2::Repository,
+    remote_name: &str,
+    remote_url: &str,
+    mirror: &bool,
+
+    username: &str,
+    password: &str,
+    ssh_private_key: &ssh_key::PrivateKey,
+) -> GhRsResult<()> {
+    let default_branch_name = git2_default_branch_name(repo)?;
+    if *mirror {
+        // remote tracking branches are deleted with the remote recreation, keep them as local ones
+        for (branch, _) in repo.branches(Some(git2::BranchType::Remote))?.flatten() {
+            let name = match branch.name() {
+                Ok(Some(n)) => n.to_string(),
+                _ => continue,
+            };
+            let local_name = match name.split_once('/') {
+                Some((_, n)) if n != "HEAD" => n,
+                _ => continue,
+            };
+            if repo.find_branch(local_name, git2::BranchType::Local).is_ok() { continue; }
+            repo.branch(local_name, &branch.get().peel_to_commit()?, false)?;
+        }
+    }
+    let refspecs = git2_refspecs(repo, &default_branch_name, mirror, mirror)?;
+    git2_push_refspecs(repo, remote_name, remote_url, &refspecs, username, password, ssh_private_key)
+}
+
+fn git2_refspecs(
+    repo: &git2::Repository,
+    branch_name: &str,
+    all_branches: &bool,
+    tags: &bool,
+) -> GhRsResult<Vec<String>> {
+    let mut refspecs: Vec<String> = vec![];
+    refspecs.push(format!("refs/heads/{branch_name}"));
+    if *all_branches {
+        for (branch, _) in repo.branches(Some(git2::BranchType::Local))?.flatten() {
+            if let Ok(Some(name)) = branch.name() {
+                if name == branch_name { continue; }
+                refspecs.push(format!("refs/heads/{name}:refs/heads/{name}"));
+            }
+        }
+    }
+    if *tags {
+        let tags = repo.tag_names(None)?;
+        for tag in tags.iter().flatten() {
+            refspecs.push(format!("refs/tags/{tag}:refs/tags/{tag}"));
+        }
+    }
+    Ok(refspecs)
+}
+
+fn git2_push_refspecs(
+    repo: &git2::Repository,
+    remote_name: &str,
+    remote_url: &str,
+    refspecs: &[String],
+
+    username: &str,
+    password: &str,
+    ssh_private_key: &ssh_key::PrivateKey,
+) -> GhRsResult<()> {
+    // remote already pointing to url keeps its tracking branches
+    let mut remote = match repo.find_remote(remote_name) {
+        Ok(r) if r.url().map(|u| github_git_urls_match(u, remote_url)).unwrap_or(false) => r,
+        _ => git2_remote_recreate(repo, remote_name, remote_url)?,
+    };
+    let mut opts = git2::PushOptions::new();
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed| 
+        git2_credentials(
+            url, username_from_url, allowed,
+            username, password, ssh_private_key
+        )
+     );
+    opts.remote_callbacks(callbacks);
+    remote.push(refspecs, Some(&mut opts))?;
+    Ok(())
+}
+
+
+fn git2_clone(
+    clone_url: &str,
+    clone_to_path: &path::PathBuf,
+    clone_bare: &bool,
+
+    username: &str,
+    password: &str,
+    ssh_private_key: &ssh_key::PrivateKey,
+) -> GhRsResult<git2::Repository> {
+    let mut fetch_options = git2::FetchOptions::new();
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed| 
+        git2_credentials(
+            url, username_from_url, allowed,
+            username, password, ssh_private_key
+        )
+    );
+    fetch_options.remote_callbacks(callbacks);
+    let checkout_builder = git2::build::CheckoutBuilder::new();
+    let repo = git2::build::RepoBuilder::new()
+        .bare(*clone_bare)
+        .remote_create(git2_remote_recreate)
+        .fetch_options(fetch_options)
+        .with_checkout(checkout_builder)
+        .clone(clone_url, path_to_sync(clone_to_path).as_path())?;
+
+    Ok(repo)
+}
+
+fn git2_remote_is_reachable(
+    url: &str,
+
+    username: &str,
+    password: &str,
+    ssh_private_key: &ssh_key::PrivateKey,
+) -> bool {
+    let mut remote = match git2::Remote::create_detached(url) {
+        Ok(r) => r,
+        Err(_) => return false,
+    };
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed| 
+        git2_credentials(
+            url, username_from_url, allowed,
+            username, password, ssh_private_key
+        )
+    );
+    let is_reachable = remote.connect_auth(git2::Direction::Fetch, Some(callbacks), None).is_ok();
+    is_reachable
+}
+
+async fn git2_lfs_transfer(
+    repo: &git2::Repository,
+    source_endpoint_opt: Option<&str>,
+    destination_endpoint: &str,
+    ref_globs: &[&str],
+
+    username: &str,
+    password: &str,
+) -> GhRsResult<usize> {
+    let lfs_pointers = lfs::pointers_reachable(repo, ref_globs)?;
+    if let Some(source_endpoint) = source_endpoint_opt {
+        lfs::download(repo, source_endpoint, &lfs_pointers, username, password).await?;
+    }
+    lfs::upload(repo, destination_endpoint, &lfs_pointers, username, password).await
+}
+
+async fn git2_lfs_checkout(
+    repo: &git2::Repository,
+    remote_url: &str,
+
+    username: &str,
+    password: &str,
+) -> GhRsResult<usize> {
+    let lfs_pointers = lfs::pointers_in_tree(repo, &repo.head()?.peel_to_tree()?)?;
+    let endpoint = match lfs::endpoint_for_source(repo, remote_url) {
+        Some(e) => e,
+        None => return_cmd_err!("Failed to find LFS server for {remote_url}"),
+    };
+    lfs::download(repo, &endpoint, &lfs_pointers, username, password).await?;
+    lfs::smudge(repo)
+}
+
+fn git2_clone_with_credential_helpers(
+    clone_url: &str,
+    clone_to_path: &path::PathBuf,
+    clone_bare: &bool,
+) -> GhRsResult<git2::Repository> {
+    let config = git2::Config::open_default()?;
+    let mut fetch_options = git2::FetchOptions::new();
+    let mut callbacks = git2::RemoteCallbacks::new();
+    // libgit2 asks again on every rejection, don't loop forever on wrong credentials
+    let mut attempts = 0;
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        attempts += 1;
+        if attempts > 3 {
+            return Err(git2::Error::from_str("authentication failed, check git credential helpers or ssh-agent"));
+        }
+        git2_credentials_from_helpers(url, username_from_url, allowed, &config)
+    });
+    fetch_options.remote_callbacks(callbacks);
+    let repo = git2::build::RepoBuilder::new()
+        .bare(*clone_bare)
+        .remote_create(git2_remote_recreate)
+        .fetch_options(fetch_options)
+        .clone(clone_url, path_to_sync(clone_to_path).as_path())?;
+
+    Ok(repo)
+}
+
+fn git2_credential_helpers_userpass(url: &str) -> Option<(String, String)> {
+    let config = git2::Config::open_default().ok()?;
+    git2::CredentialHelper::new(url).config(&config).execute()
+}
+
+async fn git2_fetch_until_commit<'repo>(
+    repo: &'repo git2::Repository,
+    remote_name: &str,
+    remote_url: &str,
+    commit_id: &git2::Oid,
+    retry_timeout_duration: &tokio::time::Duration,
+    retries_limit: usize,
+
+    username: &str,
+    password: &str,
+    ssh_private_key: &ssh_key::PrivateKey,
+) -> GhRsResult<()> {
+    if repo.find_commit(commit_id.to_owned()).is_ok() { return Ok(()); }
+
+    let mut remote = git2_remote_recreate(repo, remote_name, remote_url)?;
+
+    let mut retries = 0;
+    while retries <= retries_limit {
+        let started_at = tokio::time::Instant::now();
+        remote.connect(git2::Direction::Fetch)?;
+        let mut fetch_options = git2::FetchOptions::new();
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(move |url, username_from_url, allowed| 
+            git2_credentials(
+                url, username_from_url, allowed,
+                username, password, ssh_private_key
+            )
+        );
+        fetch_options.remote_callbacks(callbacks);
+        remote.download(&[] as &[&str], Some(&mut fetch_options))?;
+        remote.disconnect()?;
+        remote.update_tips(None, true, git2::AutotagOption::Unspecified, None)?;
+
+        if repo.find_commit(commit_id.to_owned()).is_ok() { return Ok(()); }
+        
+        tokio::time::sleep_until(started_at + retry_timeout_duration.to_owned()).await;
+        retries += 1;
+    }
+    return_cmd_err!("Retries limit reached")
+}
+    
+
+async fn get_auth(
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<(octocrab::Octocrab, String, octocrab::models::Author)> {
+    let cli_only_error = GhRsError::CliOnly("access token".to_string());
+
+    async fn prompt_token() -> String {
+        loop {
+            let token_answer = prompt("GitHub access token", None).await.trim().to_string();
+            if token_answer.len() != 40 || !re_token().is_match(&token_answer) {
+                aprintln!("Invalid token format");
+                continue;
+            }
+            return token_answer;
+        }
+    }
+    
+    let mut tried_env = false;
+    let mut tried_config = false;
+    let mut tried_credentials = false;
+    let mut tried_opt = false;
+    loop {
+        let token_raw = match token_raw_opt {
+            None => match get_github_token_from_machine(
+                &mut tried_env, 
+                &mut tried_config, 
+                &mut tried_credentials
+            ).await {
+                Some(token_found) => token_found,
+                None => match *cli_only {
+                    true => return Err(cli_only_error),
+                    false => prompt_token().await,
+                },
+            },
+            Some(token_raw_possible) => {
+                if !tried_opt && token_raw_possible.len() == 40 && re_token().is_match(token_raw_possible) {
+                    tried_opt = true;
+                    token_raw_possible.to_string()
+                } else {
+                    match get_github_token_from_machine(
+                        &mut tried_env, 
+                        &mut tried_config, 
+                        &mut tried_credentials
+                    ).await {
+                        Some(token_found) => token_found,
+                        None => match *cli_only {
+                            true => return Err(cli_only_error),
+                            false => prompt_token().await,
+                        },
+                    }
+                }
+            }
+        };
+
+        let octocrab_client = octocrab::OctocrabBuilder::default()
+            .personal_token(token_raw.clone())
+            .build()?;
+        match octocrab_client.current().user().await {
+            Ok(user) => {
+                return Ok((octocrab_client, token_raw, user));
+            }
+            Err(e) => {
+                aprintln!("Invalid answer received from API, possible access token is wrong, error: {e}");
+                if *cli_only {
+                    return Err(cli_only_error);
+                }
+                continue;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RepoCreateOptionsRaw {
+    pub profile: Option<String>,
+    pub homepage: Option<String>,
+    pub has_issues: Option<bool>,
+    pub has_projects: Option<bool>,
+    pub has_wiki: Option<bool>,
+    pub has_discussions: Option<bool>,
+    pub is_template: Option<bool>,
+    pub merge_methods: Option<String>,
+    pub delete_branch_on_merge: Option<bool>,
+    pub allow_auto_merge: Option<bool>,
+    pub visibility: Option<String>,
+    pub secret_scanning: Option<bool>,
+    pub push_protection: Option<bool>,
+}
+
+fn get_repo_create_options_raw(submatches: &clap::ArgMatches) -> RepoCreateOptionsRaw {
+    RepoCreateOptionsRaw {
+        profile: submatches.get_one::<String>("profile").cloned(),
+        homepage: submatches.get_one::<String>("homepage").cloned(),
+        has_issues: submatches.get_one::<bool>("issues").copied(),
+        has_projects: submatches.get_one::<bool>("projects").copied(),
+        has_wiki: submatches.get_one::<bool>("wiki").copied(),
+        has_discussions: submatches.get_one::<bool>("discussions").copied(),
+        is_template: submatches.get_one::<bool>("is_template").copied(),
+        merge_methods: submatches.get_one::<String>("merge_methods").cloned(),
+        delete_branch_on_merge: submatches.get_one::<bool>("delete_branch_on_merge").copied(),
+        allow_auto_merge: submatches.get_one::<bool>("auto_merge").copied(),
+        visibility: submatches.get_one::<String>("visibility").cloned(),
+        secret_scanning: submatches.get_one::<bool>("secret_scanning").copied(),
+        push_protection: submatches.get_one::<bool>("push_protection").copied(),
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+const merge_methods: [&str; 3] = ["merge", "squash", "rebase"];
+
+// "squash,rebase" -> (allow_merge_commit, allow_squash_merge, allow_rebase_merge)
+fn parse_merge_methods(merge_methods_raw: &str) -> GhRsResult<(bool, bool, bool)> {
+    let methods: Vec<&str> = merge_methods_raw.split(',')
+        .map(|m| m.trim())
+        .filter(|m| !m.is_empty())
+        .collect();
+    if methods.is_empty() {
+        return_cmd_err!("At least one merge method has to be allowed");
+    }
+    if let Some(method) = methods.iter().find(|m| !merge_methods.contains(m)) {
+        return_cmd_err!(
+            "Invalid merge method \"{method}\", expected {merge_methods_list}",
+            merge_methods_list = merge_methods.join(", ")
+        );
+    }
+    Ok((methods.contains(&"merge"), methods.contains(&"squash"), methods.contains(&"rebase")))
+}
+
+// Flags override [profile.<name>] section of gh.rs config, [profile.default] is used without --profile:
+//   [profile.work]
+//   visibility = internal
+//   merge-methods = squash
+//   delete-branch-on-merge = true
+async fn get_repo_create_options(
+    options_raw: &RepoCreateOptionsRaw,
+    public_raw: &bool,
+    org_raw_opt: &Option<&str>,
+) -> GhRsResult<OctocrabReposCreateOptions> {
+    let profile_name = options_raw.profile.as_deref().unwrap_or("default");
+    let section_name = format!("profile.{profile_name}");
+    let config_opt = get_gh_rs_config().await;
+    let has_section = config_opt.as_ref()
+        .map(|c| c.get_map_ref().contains_key(&section_name))
+        .unwrap_or(false);
+    if options_raw.profile.is_some() && !has_section {
+        return_cmd_err!("Profile \"{profile_name}\" not found, add [{section_name}] to ~/.config/gh.rs/config.ini");
+    }
+    let profile_get = |key: &str| config_opt.as_ref().and_then(|c| c.get(&section_name, key));
+    let profile_get_bool = |key: &str| -> GhRsResult<Option<bool>> {
+        match profile_get(key) {
+            Some(value) => match parse_bool(&value) {
+                Some(b) => Ok(Some(b)),
+                None => return_cmd_err!("Invalid \"{key}\" value \"{value}\" in [{section_name}], expected true or false"),
+            },
+            None => Ok(None),
+        }
+    };
+
+    let (allow_merge_commit, allow_squash_merge, allow_rebase_merge) = match options_raw.merge_methods.clone().or(profile_get("merge-methods")) {
+        Some(m) => {
+            let (merge, squash, rebase) = parse_merge_methods(&m)?;
+            (Some(merge), Some(squash), Some(rebase))
+        },
+        None => (None, None, None),
+    };
+    // explicit --public wins over profile
+    let visibility = match *public_raw {
+        true => None,
+        false => options_raw.visibility.clone().or(profile_get("visibility")).map(|v| v.trim().to_lowercase()),
+    };
+    match visibility.as_deref() {
+        None | Some("public") | Some("private") => {},
+        Some("internal") if org_raw_opt.is_none() => return_cmd_err!("Internal visibility is only available for organization repos, set --org"),
+        Some("internal") => {},
+        Some(v) => return_cmd_err!("Invalid visibility \"{v}\", expected public, private or internal"),
+    }
+    Ok(OctocrabReposCreateOptions {
+        homepage: options_raw.homepage.clone().or(profile_get("homepage")),
+        has_issues: options_raw.has_issues.or(profile_get_bool("issues")?),
+        has_projects: options_raw.has_projects.or(profile_get_bool("projects")?),
+        has_wiki: options_raw.has_wiki.or(profile_get_bool("wiki")?),
+        has_discussions: options_raw.has_discussions.or(profile_get_bool("discussions")?),
+        is_template: options_raw.is_template.or(profile_get_bool("is-template")?),
+        allow_merge_commit,
+        allow_squash_merge,
+        allow_rebase_merge,
+        delete_branch_on_merge: options_raw.delete_branch_on_merge.or(profile_get_bool("delete-branch-on-merge")?),
+        allow_auto_merge: options_raw.allow_auto_merge.or(profile_get_bool("auto-merge")?),
+        visibility,
+        secret_scanning: options_raw.secret_scanning.or(profile_get_bool("secret-scanning")?),
+        secret_scanning_push_protection: options_raw.push_protection.or(profile_get_bool("push-protection")?),
+    })
+}
+
+// Visibility from options or profile skips the question
+async fn get_is_public_with_options(
+    public_raw: &bool,
+    create_options: &OctocrabReposCreateOptions,
+    cli_only: &bool,
+) -> bool {
+    match create_options.visibility.as_deref() {
+        Some(visibility) => visibility == "public",
+        None => get_is_public(public_raw, false, cli_only).await,
+    }
+}
+
+// Org when given (checked to be accessible), user otherwise
+async fn get_repo_owner(
+    octocrab_client: &octocrab::Octocrab,
+    username: &str,
+    org_raw_opt: &Option<&str>,
+) -> GhRsResult<String> {
+    let org = match org_raw_opt {
+        Some(o) => o.trim(),
+        None => return Ok(username.to_string()),
+    };
+    match octocrab_client.orgs(org).get().await {
+        Ok(gh_org) => Ok(gh_org.login),
+        Err(e) => return_cmd_err!("Organization {org} is unavailable, error: {e}"),
+    }
+}
+
+const team_permissions: [&str; 5] = ["pull", "triage", "push", "maintain", "admin"];
+
+// "slug:permission" pairs, permission defaults to push
+fn get_teams(
+    teams_raw: &Vec<&str>,
+    org_raw_opt: &Option<&str>,
+) -> GhRsResult<Vec<(String, String)>> {
+    if !teams_raw.is_empty() && org_raw_opt.is_none() {
+        return_cmd_err!("Team access needs an organization, set it with --org");
+    }
+    let mut teams = vec![];
+    for team_raw in teams_raw {
+        let (team_slug, permission) = team_raw.split_once(':').unwrap_or((team_raw, "push"));
+        let (team_slug, permission) = (team_slug.trim(), permission.trim().to_lowercase());
+        if team_slug.is_empty() {
+            return_cmd_err!("Invalid team \"{team_raw}\", expected slug:permission");
+        }
+        if !team_permissions.contains(&permission.as_str()) {
+            return_cmd_err!(
+                "Invalid team permission \"{permission}\", expected one of {permissions}",
+                permissions = team_permissions.join(", ")
+            );
+        }
+        teams.push((team_slug.to_string(), permission));
+    }
+    Ok(teams)
+}
+
+async fn apply_teams(
+    octocrab_client: &octocrab::Octocrab,
+    org: &str,
+    repo_name: &str,
+    teams: &Vec<(String, String)>,
+) -> GhRsResult<()> {
+    for (team_slug, permission) in teams {
+        let _ = match octocrab_teams_add_repo(octocrab_client, org, team_slug, repo_name, permission).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to grant {team_slug} team {permission} access, error: {e}"),
+        };
+        aprintln!("Granted {team_slug} team {permission} access");
+    }
+    Ok(())
+}
+
+// "user:permission" pairs, permission defaults to push
+fn get_invites(invites_raw: &Vec<&str>) -> GhRsResult<Vec<(String, String)>> {
+    let mut invites = vec![];
+    for invite_raw in invites_raw {
+        let (login, permission) = invite_raw.split_once(':').unwrap_or((invite_raw, "push"));
+        let (login, permission) = (login.trim(), permission.trim().to_lowercase());
+        if !re_username().is_match(login) {
+            return_cmd_err!("Invalid invite \"{invite_raw}\", expected user:permission");
+        }
+        if !team_permissions.contains(&permission.as_str()) {
+            return_cmd_err!(
+                "Invalid invite permission \"{permission}\", expected one of {permissions}",
+                permissions = team_permissions.join(", ")
+            );
+        }
+        invites.push((login.to_string(), permission));
+    }
+    Ok(invites)
+}
+
+async fn apply_invites(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    invites: &Vec<(String, String)>,
+) -> GhRsResult<()> {
+    for (login, permission) in invites {
+        let is_invited = match octocrab_collaborators_put(octocrab_client, owner, repo_name, login, permission).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to invite {login}, error: {e}"),
+        };
+        match is_invited {
+            true => aprintln!("Invited {login} with {permission} access"),
+            false => aprintln!("Granted {login} {permission} access"),
+        }
+    }
+    Ok(())
+}
+
+async fn get_repo_name(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name_raw_opt: &Option<&str>,
+    repo_name_prompt_default: Option<&str>,
+    check_local_dir: &bool,
+    allow_existing_empty: &bool,
+    cli_only: &bool,
+) -> GhRsResult<String> {
+    let cli_only_error = GhRsError::CliOnly("new repo name".to_string());
+
+    async fn prompt_repo_name(prompt_default: Option<&str>, check_local_dir: &bool) -> String {
+        loop {
+            let repo_name_answer = prompt("GitHub new repo name", prompt_default).await.trim().to_string();
+            if repo_name_answer.is_empty() {
+                aprintln!("Invalid name format, empty string");
+                continue;
+            } else if !re_repo_name().is_match(&repo_name_answer) {
+                aprintln!("Invalid name format");
+                continue;
+            } else if *check_local_dir && path!("./{repo_name_answer}").exists().await {
+                aprintln!("Directory with name {repo_name_answer} already exists");
+                continue;
+            }
+            return repo_name_answer;
+        }
+    }
+
+    let mut repo_name_raw_opt = *repo_name_raw_opt;
+    loop {
+        let repo_name_raw = match repo_name_raw_opt {
+            Some(repo_name_raw_possible) => match re_repo_name().is_match(repo_name_raw_possible) {
+                true => repo_name_raw_possible.to_string(),
+                false => {
+                    aprintln!("Invalid name format");
+                    match *cli_only {
+                        true => return Err(cli_only_error),
+                        false => prompt_repo_name(repo_name_prompt_default, check_local_dir).await,
+                    }
+                },
+            }
+            None => match *cli_only {
+                true => return Err(cli_only_error),
+                false => prompt_repo_name(repo_name_prompt_default, check_local_dir).await,
+            },
+        };
+        repo_name_raw_opt = None;
+        match octocrab_client.repos(owner, repo_name_raw.clone()).get().await {
+            Err(e) => {
+                return Ok(repo_name_raw);
+            }
+            Ok(repo) => {
+                // empty repo can still be pushed into, caller asks about it
+                if *allow_existing_empty && octocrab_repos_is_empty(octocrab_client, owner, &repo_name_raw).await.unwrap_or(false) {
+                    return Ok(repo_name_raw);
+                }
+                aprintln!("Repo with name {repo_name_raw} already exists");
+                if *cli_only {
+                    return Err(cli_only_error);
+                }
+                continue;
+            }
+        }
+    }
+}
+
+async fn get_repo_description(
+    repo_description_raw_opt: &Option<&str>,
+    repo_description_prompt_default: Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<String> {
+    let cli_only_error = GhRsError::CliOnly("new repo description".to_string());
+
+    async fn prompt_repo_description(prompt_default: Option<&str>) -> String {
+        loop {
+            let mut repo_description_answer = prompt("GitHub new repo description", prompt_default).await;
+            repo_description_answer = repo_description_answer.trim().to_string();
+            if repo_description_answer.len() <= 255 {
+                return repo_description_answer;
+            }
+            aprintln!("Too long description, maximum lenght 255, now: {len}", len = repo_description_answer.len());
+            continue;
+        }
+    }
+    
+    // manifest descriptions may be longer than GitHub allows
+    let repo_description_prompt_default = repo_description_prompt_default.filter(|d| d.len() <= 255);
+    let repo_description = match repo_description_raw_opt.or(match *cli_only {
+        true => repo_description_prompt_default,
+        false => None,
+    }) {
+        Some(repo_description_raw) => {
+            if repo_description_raw.len() <= 255 {
+                repo_description_raw.to_string()
+            } else {
+                aprintln!("Too long description, maximum lenght 255, now: {len}", len = repo_description_raw.len());
+                match *cli_only {
+                    true => return Err(cli_only_error),
+                    false => prompt_repo_description(repo_description_prompt_default).await,
+                }
+            }
+        }
+        None => match *cli_only {
+            true => return Err(cli_only_error),
+            false => prompt_repo_description(repo_description_prompt_default).await,
+        }
+    };
+    Ok(repo_description)
+}
+
+async fn get_repo_topics(
+    repo_topics_raw_opt: &Option<&str>,
+    suggested_topics: &[String],
+    cli_only: &bool,
+) -> GhRsResult<Vec<String>> {
+    fn parse_topics(topics_raw: &str) -> Result<Vec<String>, String> {
+        let mut topics = vec![];
+        for topic_raw in topics_raw.split([',', ' ']).map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            match project::topic_slug(topic_raw) {
+                Some(t) if t == topic_raw.to_lowercase() => topics.push(t),
+                _ => return Err(format!("Invalid topic \"{topic_raw}\", use lowercase letters, numbers & hyphens, up to 50 chars")),
+            }
+        }
+        if topics.len() > 20 {
+            return Err(format!("Too many topics, maximum 20, now: {len}", len = topics.len()));
+        }
+        Ok(topics)
+    }
+
+    let suggested_topics_str = suggested_topics.join(",");
+    if let Some(repo_topics_raw) = repo_topics_raw_opt {
+        match parse_topics(repo_topics_raw) {
+            Ok(topics) => return Ok(topics),
+            Err(e) => {
+                aprintln!("{e}");
+                if *cli_only {
+                    return Err(GhRsError::CliOnly("valid repo topics".to_string()));
+                }
+            }
+        }
+    } else if *cli_only {
+        return Ok(suggested_topics.to_vec());
+    }
+    loop {
+        let prompt_default = Some(suggested_topics_str.as_str()).filter(|t| !t.is_empty());
+        let repo_topics_answer = prompt("GitHub repo topics, comma separated", prompt_default).await;
+        match parse_topics(&repo_topics_answer) {
+            Ok(topics) => return Ok(topics),
+            Err(e) => {
+                aprintln!("{e}");
+                continue;
+            }
+        }
+    }
+}
+
+fn resolve_github_path<'path>(github_path_or_url: &'path str) -> Option<(&'path str, &'path str)> {
+    let mut path = github_path_or_url;
+    path = path.strip_prefix("https://").unwrap_or(path);
+    path = path.strip_prefix("http://").unwrap_or(path);
+    path = path.strip_prefix("github.com").unwrap_or(path);
+    path = path.strip_prefix("/").unwrap_or(path);
+    path = path.strip_suffix("/").unwrap_or(path);
+    if path.contains("?") {
+        path = path.split_once("?").expect("found one").0;
+        path = path.strip_suffix("/").unwrap_or(path);
+    }
+    if path.contains("#") {
+        path = path.split_once("#").expect("found one").0;
+        path = path.strip_suffix("/").unwrap_or(path);
+    }
+    let mut path_splitter = path.splitn(3, "/");
+    let username = match path_splitter.next() {
+        Some(s) => s,
+        None => return None,
+    };
+    let mut repo_name = match path_splitter.next() {
+        Some(s) => s,
+        None => return None,
+    };
+    repo_name = repo_name.strip_suffix(".git").unwrap_or(repo_name);
+    if !re_username().is_match(username) { return None; }
+    if !re_repo_name().is_match(repo_name) { return None; }
+    Some((username, repo_name))
+}
+
+async fn get_external_path(
+    octocrab_client: &octocrab::Octocrab,
+    external_path_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<(String, String, octocrab::models::Repository)> {
+    let cli_only_error = GhRsError::CliOnly("external repo url".to_string());
+
+    async fn prompt_external_path() -> (String, String) {
+        loop {
+            let external_url_answer = prompt("GitHub external repo url", None).await.trim().to_string();
+            match resolve_github_path(&external_url_answer) {
+                Some((username_str, repo_name_str)) => return (username_str.to_string(), repo_name_str.to_string()),
+                None => {
+                    aprintln!(
+                        "Invalid url format, expected {ex_url} or just {ex_repo_path}",
+                        ex_url = get_gh_rs_github_url(),
+                        ex_repo_path = get_gh_rs_github_repo_path(),
+                    );
+                    continue;
+                }
+            }
+        }
+    }
+
+    loop {
+        let (external_username, external_repo_name) = match external_path_raw_opt {
+            Some(external_path_raw_possible) => match resolve_github_path(external_path_raw_possible) {
+                None => {
+                    aprintln!(
+                        "Invalid url format, expected {ex_url} or just {ex_repo_path}",
+                        ex_url = get_gh_rs_github_url(),
+                        ex_repo_path = get_gh_rs_github_repo_path(),
+                    );
+                    match *cli_only {
+                        true => return Err(cli_only_error),
+                        false => prompt_external_path().await,
+                    }
+                },
+                Some((external_username_str, external_repo_name_str)) => (external_username_str.to_string(), external_repo_name_str.to_string()),
+            }
+            None => match *cli_only {
+                true => return Err(cli_only_error),
+                false => prompt_external_path().await,
+            }
+        };
+        match octocrab_client.repos(external_username.clone(), external_repo_name.clone()).get().await {
+            Err(e) => {
+                aprintln!(
+                    "Repo {username}/{repo_name} is unavailable", 
+                    username = external_username.clone(), 
+                    repo_name = external_repo_name.clone()
+                );
+                if *cli_only {
+                    return Err(cli_only_error);
+                }
+                continue;
+            }
+            Ok(repo) => {
+                return Ok((external_username, external_repo_name, repo));
+            }
+        }
+    }
+}
+
+fn resolve_import_source(source_url_or_path: &str) -> Option<(String, String)> {
+    let source = source_url_or_path.trim();
+    if source.is_empty() { return None; }
+    // scp-like "host:path" has a colon before any slash, as git decides it, except windows drive letters
+    let is_scp_like = match source.split_once(':') {
+        Some((host, _)) => host.len() > 1 && !host.contains('/') && !host.contains('\\'),
+        None => false,
+    };
+    let is_url = source.contains("://") || is_scp_like;
+    let source_url = match is_url {
+        true => source.to_string(),
+        false => {
+            let source_path = path_to_sync(&path::PathBuf::from(source));
+            let source_path_canonical = std::fs::canonicalize(&source_path).ok()?;
+            // accept only bare or non-bare git repos
+            git2::Repository::open(&source_path_canonical).ok()?;
+            source_path_canonical.to_str()?.to_string()
+        }
+    };
+    let mut name = source_url.as_str();
+    name = name.strip_suffix("/").unwrap_or(name);
+    name = name.strip_suffix("/.git").unwrap_or(name);
+    name = name.strip_suffix(".git").unwrap_or(name);
+    name = name.rsplit(['/', ':']).next().unwrap_or("");
+    Some((source_url.clone(), name.to_string()))
+}
+
+async fn get_import_source(
+    source_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<(String, String)> {
+    let cli_only_error = GhRsError::CliOnly("import source url or path".to_string());
+
+    async fn prompt_import_source() -> (String, String) {
+        loop {
+            let source_answer = prompt("Git repo url or local path to import", None).await.trim().to_string();
+            match resolve_import_source(&source_answer) {
+                Some(r) => return r,
+                None => {
+                    aprintln!("Invalid source, expected git url (https://, ssh://, git@host:path, file://) or path to local git repo");
+                    continue;
+                }
+            }
+        }
+    }
+
+    let source = match source_raw_opt {
+        Some(source_raw) => match resolve_import_source(source_raw) {
+            Some(r) => r,
+            None => {
+                aprintln!("Invalid source, expected git url (https://, ssh://, git@host:path, file://) or path to local git repo");
+                match *cli_only {
+                    true => return Err(cli_only_error),
+                    false => prompt_import_source().await,
+                }
+            }
+        },
+        None => match *cli_only {
+            true => return Err(cli_only_error),
+            false => prompt_import_source().await,
+        }
+    };
+    Ok(source)
+}
+
+async fn get_secret_rules() -> GhRsResult<Vec<secrets::SecretRule>> {
+    let mut rules = secrets::builtin_rules();
+    // [secret_rules] section of gh.rs config, "name = regex" per line
+    if let Some(config) = get_gh_rs_config().await {
+        if let Some(rules_section) = config.get_map_ref().get("secret_rules") {
+            let rules_raw: Vec<(String, String)> = rules_section.iter()
+                .filter_map(|(name, regex)| regex.clone().map(|r| (name.clone(), r)))
+                .collect();
+            rules.extend(secrets::user_rules(&rules_raw)?);
+        }
+    }
+    Ok(rules)
+}
+
+async fn check_secret_findings(
+    findings: &[secrets::SecretFinding],
+    allow_secrets: &bool,
+) -> GhRsResult<()> {
+    if findings.is_empty() {
+        return Ok(());
+    }
+    aprintln!("Found {count} possible secrets:", count = findings.len());
+    for finding in findings {
+        let commit = finding.commit_id
+            .map(|id| format!(" (commit {short_id})", short_id = &id.to_string()[..7]))
+            .unwrap_or_default();
+        aprintln!(
+            "  {path}:{line} {rule_name}: {excerpt}{commit}",
+            path = finding.path,
+            line = finding.line,
+            rule_name = finding.rule_name,
+            excerpt = finding.excerpt,
+        );
+    }
+    if !*allow_secrets {
+        return_cmd_err!("Refusing to publish possible secrets, remove them (and rewrite history if committed) or pass --allow-secrets");
+    }
+    aprintln!("Publishing anyway, --allow-secrets is set");
+    Ok(())
+}
+
+fn format_size(size: usize) -> String {
+    match size {
+        s if s >= 1024 * 1024 * 1024 => format!("{:.1} GB", s as f64 / (1024 * 1024 * 1024) as f64),
+        s if s >= 1024 * 1024 => format!("{:.1} MB", s as f64 / (1024 * 1024) as f64),
+        s if s >= 1024 => format!("{:.1} KB", s as f64 / 1024_f64),
+        s => format!("{s} B"),
+    }
+}
+
+// Columns padded to widest cell, separated by 2 spaces
+fn format_table(header: &[&str], rows: &[Vec<String>]) -> Vec<String> {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+    let header_row: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    std::iter::once(&header_row).chain(rows.iter())
+        .map(|row| {
+            let cells: Vec<String> = row.iter().enumerate().map(|(i, cell)| format!("{cell:<width$}", width = widths[i])).collect();
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+// "500", "100K", "10M", "1G"
+fn parse_size(size_raw: &str) -> Option<usize> {
+    let size_raw = size_raw.trim().to_uppercase();
+    let size_raw = size_raw.strip_suffix("B").unwrap_or(&size_raw);
+    let (number, multiplier) = match size_raw.chars().last()? {
+        'K' => (&size_raw[..size_raw.len() - 1], 1024),
+        'M' => (&size_raw[..size_raw.len() - 1], 1024 * 1024),
+        'G' => (&size_raw[..size_raw.len() - 1], 1024 * 1024 * 1024),
+        _ => (size_raw, 1),
+    };
+    number.trim().parse::<usize>().ok().map(|n| n * multiplier)
+}
+
+fn get_rewrite_options(
+    strip_paths: &[&str],
+    strip_blobs_bigger_than_raw_opt: &Option<&str>,
+    mailmap_path_raw_opt: &Option<&str>,
+) -> GhRsResult<rewrite::RewriteOptions> {
+    let strip_blobs_bigger_than = match strip_blobs_bigger_than_raw_opt {
+        Some(size_raw) => match parse_size(size_raw) {
+            Some(size) => Some(size),
+            None => return_cmd_err!("Invalid size \"{size_raw}\", expected e.g. 500K, 10M or 1G"),
+        },
+        None => None,
+    };
+    Ok(rewrite::RewriteOptions {
+        strip_paths: strip_paths.iter().map(|p| p.to_string()).collect(),
+        strip_blobs_bigger_than,
+        mailmap_path: mailmap_path_raw_opt.map(|p| p.to_string()),
+    })
+}
+
+async fn apply_history_rewrite(
+    repo: &git2::Repository,
+    ref_names: &[&str],
+    rewrite_options: &rewrite::RewriteOptions,
+) -> GhRsResult<()> {
+    if rewrite_options.is_empty() {
+        return Ok(());
+    }
+    let mailmap = match &rewrite_options.mailmap_path {
+        Some(mailmap_path) => match fs::read_to_string(path!("{mailmap_path}")).await {
+            Ok(mailmap_text) => Some(git2::Mailmap::from_buffer(&mailmap_text)?),
+            Err(e) => return_cmd_err!("Failed to read mailmap {mailmap_path}, error: {e}"),
+        },
+        None => None,
+    };
+    let head_id_before = repo.head().ok().and_then(|h| h.target());
+    let commit_map = match rewrite::rewrite_history(repo, ref_names, rewrite_options, mailmap.as_ref()) {
+        Ok(m) => m,
+        Err(e) => return_cmd_err!("Failed to rewrite history, error: {e}"),
+    };
+    if commit_map.is_empty() {
+        aprintln!("History rewrite changed nothing");
+        return Ok(());
+    }
+    aprintln!("Rewritten commits (old -> new), originals are kept in refs/original/:");
+    for (old_id, new_id) in &commit_map {
+        aprintln!("  {old_id} -> {new_id}");
+    }
+    // keep working tree files, but index has to follow the new HEAD
+    let head_commit = repo.head()?.peel_to_commit()?;
+    if repo.workdir().is_some() && head_id_before != Some(head_commit.id()) {
+        repo.reset(head_commit.as_object(), git2::ResetType::Mixed, None)?;
+        if !rewrite_options.strip_paths.is_empty() {
+            aprintln!("Stripped paths are left in the working tree as untracked files, consider adding them to .gitignore");
+        }
+    }
+    Ok(())
+}
+
+// GitHub warns about files over 50 MB, rejects files over 100 MB & pushes over 2 GB
+async fn check_push_size(
+    repo: &git2::Repository,
+    ref_names: &[&str],
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let blob_size_warn = 50 * 1024 * 1024;
+    let blob_size_max = 100 * 1024 * 1024;
+    let pack_size_max = 2 * 1024 * 1024 * 1024;
+
+    // building the pack just to measure it would double the push time, uncompressed size is an upper bound
+    let (large_blobs, pack_size) = match git2_history_size(repo, ref_names, blob_size_warn) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to count push size, error: {e}"),
+    };
+    aprintln!("Push size: up to {size}", size = format_size(pack_size));
+    if large_blobs.is_empty() && pack_size <= pack_size_max {
+        return Ok(());
+    }
+
+    // only files over 100 MB are certain rejections, compressed pack may well fit in 2 GB
+    let mut rejected = false;
+    if !large_blobs.is_empty() {
+        aprintln!("Large files in history:");
+        for (path, size) in &large_blobs {
+            let verdict = match *size > blob_size_max {
+                true => {
+                    rejected = true;
+                    "rejected by GitHub, over 100 MB"
+                },
+                false => "over 50 MB",
+            };
+            aprintln!("  {path} {size} ({verdict})", size = format_size(*size));
+        }
+        aprintln!("Consider moving them to Git LFS, e.g. `git lfs migrate import --include=\"<path>\" --everything`");
+    }
+    if pack_size > pack_size_max {
+        aprintln!("Push may be over 2 GB once compressed, GitHub would reject it, then push in parts or move large files to Git LFS");
+    }
+    if !rejected {
+        return Ok(());
+    }
+    if *cli_only {
+        return_cmd_err!("Push will be rejected by GitHub because of files over 100 MB, nothing was created");
+    }
+    match prompt_bool("GitHub will reject this push, continue anyway?", Some(false)).await {
+        true => Ok(()),
+        false => return_cmd_err!("Cancelled because of push size, nothing was created"),
+    }
+}
+
+struct FileSelectionEntry {
+    path: String,
+    size: u64,
+    is_ignored: bool,
+    is_selected: bool,
+}
+
+fn dir_size_sync(dir: &std::path::Path) -> u64 {
+    let mut size = 0;
+    if let Ok(read_dir) = std::fs::read_dir(dir) {
+        for entry in read_dir.flatten() {
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => size += dir_size_sync(&entry.path()),
+                Ok(t) if t.is_file() => size += entry.metadata().map(|m| m.len()).unwrap_or(0),
+                _ => {},
+            }
+        }
+    }
+    size
+}
+
+fn dir_files_sync(dir: &std::path::Path, dir_rel: &str, files: &mut Vec<String>) {
+    if let Ok(read_dir) = std::fs::read_dir(dir) {
+        for entry in read_dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // nested repos can't be added to index
+            if name == ".git" { continue; }
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => dir_files_sync(&entry.path(), &format!("{dir_rel}{name}/"), files),
+                Ok(t) if t.is_file() => files.push(format!("{dir_rel}{name}")),
+                _ => {},
+            }
+        }
+    }
+}
+
+// Multi-select tree of untracked & modified files, ignored ones are listed unselected
+async fn get_files_selection(
+    repo: &git2::Repository,
+    cli_only: &bool,
+) -> GhRsResult<Vec<String>> {
+    if *cli_only {
+        return_cmd_err!("Interactive file selection can't be used in CLI only mode");
+    }
+    let workdir = match repo.workdir() {
+        Some(w) => w.to_path_buf(),
+        None => return_cmd_err!("Repo has no working tree"),
+    };
+    let mut status_options = git2::StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(true)
+        .recurse_ignored_dirs(false)
+        .exclude_submodules(true);
+    let statuses = repo.statuses(Some(&mut status_options))?;
+    let mut files: Vec<FileSelectionEntry> = vec![];
+    for entry in statuses.iter() {
+        if entry.status().intersects(git2::Status::WT_DELETED | git2::Status::INDEX_DELETED) { continue; }
+        let path = match entry.path() {
+            Some(p) => p.to_string(),
+            None => continue,
+        };
+        let is_ignored = entry.status().contains(git2::Status::IGNORED);
+        // ignored directories stay collapsed, e.g. "target/"
+        let size = match path.ends_with('/') {
+            true => dir_size_sync(&workdir.join(&path)),
+            false => std::fs::metadata(workdir.join(&path)).map(|m| m.len()).unwrap_or(0),
+        };
+        files.push(FileSelectionEntry { path, size, is_ignored, is_selected: !is_ignored });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    if files.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // tree rows: directories (prefix) & files (index in files)
+    let mut rows: Vec<(String, Option<usize>)> = vec![];
+    for (file_i, file) in files.iter().enumerate() {
+        let path_parts: Vec<&str> = file.path.trim_end_matches('/').split('/').collect();
+        for depth in 1..path_parts.len() {
+            let dir_prefix = format!("{}/", path_parts[..depth].join("/"));
+            if !rows.iter().any(|(p, f)| f.is_none() && *p == dir_prefix) {
+                rows.push((dir_prefix, None));
+            }
+        }
+        rows.push((file.path.clone(), Some(file_i)));
+    }
+
+    loop {
+        aprintln!("Files to commit:");
+        for (row_i, (row_path, file_i_opt)) in rows.iter().enumerate() {
+            let row_files: Vec<&FileSelectionEntry> = match file_i_opt {
+                Some(file_i) => vec![&files[*file_i]],
+                None => files.iter().filter(|f| f.path.starts_with(row_path.as_str())).collect(),
+            };
+            let selected_count = row_files.iter().filter(|f| f.is_selected).count();
+            let mark = match selected_count {
+                0 => " ",
+                c if c == row_files.len() => "x",
+                _ => "-",
+            };
+            let depth = row_path.trim_end_matches('/').matches('/').count();
+            let name = row_path.trim_end_matches('/').rsplit('/').next().unwrap_or(row_path);
+            let name = match row_path.ends_with('/') {
+                true => format!("{name}/"),
+                false => name.to_string(),
+            };
+            let size = row_files.iter().map(|f| f.size).sum::<u64>() as usize;
+            let ignored = match file_i_opt.map(|i| files[i].is_ignored).unwrap_or(false) {
+                true => ", ignored",
+                false => "",
+            };
+            aprintln!(
+                "{row_n:>4} [{mark}] {indent}{name} ({size}{ignored})",
+                row_n = row_i + 1,
+                indent = "  ".repeat(depth),
+                size = format_size(size),
+            );
+        }
+        let answer = prompt("Toggle rows (e.g. \"2 5-7\"), \"a\" all, \"n\" none, empty to commit selected", None).await;
+        let answer = answer.trim();
+        if answer.is_empty() {
+            break;
+        }
+        if answer == "a" || answer == "n" {
+            for file in files.iter_mut() {
+                file.is_selected = answer == "a";
+            }
+            continue;
+        }
+        let mut row_numbers = vec![];
+        let mut is_valid = true;
+        for part in answer.split([' ', ',']).filter(|p| !p.is_empty()) {
+            let (from, to) = part.split_once('-').unwrap_or((part, part));
+            match (from.parse::<usize>(), to.parse::<usize>()) {
+                (Ok(from), Ok(to)) if from >= 1 && from <= to && to <= rows.len() => row_numbers.extend(from..=to),
+                _ => is_valid = false,
+            }
+        }
+        if !is_valid {
+            aprintln!("Invalid rows, use numbers from 1 to {len}", len = rows.len());
+            continue;
+        }
+        for row_number in row_numbers {
+            let (row_path, file_i_opt) = &rows[row_number - 1];
+            match file_i_opt {
+                Some(file_i) => files[*file_i].is_selected = !files[*file_i].is_selected,
+                None => {
+                    let select = !files.iter().filter(|f| f.path.starts_with(row_path.as_str())).all(|f| f.is_selected);
+                    for file in files.iter_mut().filter(|f| f.path.starts_with(row_path.as_str())) {
+                        file.is_selected = select;
+                    }
+                },
+            }
+        }
+    }
+
+    let mut selected_paths = vec![];
+    for file in files.iter().filter(|f| f.is_selected) {
+        match file.path.ends_with('/') {
+            true => dir_files_sync(&workdir.join(&file.path), &file.path, &mut selected_paths),
+            false => selected_paths.push(file.path.clone()),
+        }
+    }
+    Ok(selected_paths)
+}
+
+// (template name, source), templates come from GitHub or bundled copies when offline
+async fn get_gitignore_template(
+    octocrab_client: &octocrab::Octocrab,
+    gitignore_raw_opt: &Option<&str>,
+    gitignore_default: Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<Option<(String, String)>> {
+    let cli_only_error = GhRsError::CliOnly("valid gitignore template".to_string());
+    // default is only offered in the prompt, nothing is added unasked with --cli-only
+    let mut name_raw_opt = gitignore_raw_opt.map(|n| n.to_string());
+    if name_raw_opt.is_none() && *cli_only {
+        return Ok(None);
+    }
+    let names = match octocrab_gitignore_templates(octocrab_client).await {
+        Ok(n) => n,
+        Err(e) => {
+            aprintln!("Failed to fetch gitignore templates, using bundled ones, error: {e}");
+            scaffold::bundled_gitignores.iter().map(|(n, _)| n.to_string()).collect()
+        },
+    };
+    let find_name = |name_raw: &str| names.iter().find(|n| n.eq_ignore_ascii_case(name_raw.trim())).cloned();
+
+    let name = loop {
+        let name_raw = match name_raw_opt.take() {
+            Some(n) => n,
+            None => match *cli_only {
+                true => return Ok(None),
+                false => prompt(
+                    "Gitignore template (\"?\" to list, \"none\" to skip)",
+                    Some(gitignore_default.unwrap_or("none")),
+                ).await.trim().to_string(),
+            },
+        };
+        if name_raw.eq_ignore_ascii_case("none") || name_raw.is_empty() {
+            return Ok(None);
+        } else if name_raw == "?" {
+            aprintln!("{names}", names = names.join(", "));
+            continue;
+        }
+        match find_name(&name_raw) {
+            Some(n) => break n,
+            None => {
+                aprintln!("Gitignore template \"{name_raw}\" not found");
+                if *cli_only {
+                    return Err(cli_only_error);
+                }
+            },
+        }
+    };
+    let source = match octocrab_gitignore_template(octocrab_client, &name).await {
+        Ok(t) => t.source,
+        Err(e) => match scaffold::bundled_gitignore(&name) {
+            Some(source) => source.to_string(),
+            None => return_cmd_err!("Failed to fetch gitignore template {name} and there is no bundled copy, error: {e}"),
+        },
+    };
+    Ok(Some((name, source)))
+}
+
+// (SPDX id, LICENSE text with year & holder filled in)
+async fn get_license(
+    octocrab_client: &octocrab::Octocrab,
+    license_raw_opt: &Option<&str>,
+    holder: &str,
+    cli_only: &bool,
+) -> GhRsResult<Option<(String, String)>> {
+    let cli_only_error = GhRsError::CliOnly("valid license".to_string());
+    if license_raw_opt.is_none() && *cli_only {
+        return Ok(None);
+    }
+    let licenses: Vec<(String, String, String)> = match octocrab_licenses(octocrab_client).await {
+        Ok(l) => l.into_iter()
+            .map(|l| (l.key.clone(), l.spdx_id.unwrap_or(l.key), l.name))
+            .collect(),
+        Err(e) => {
+            aprintln!("Failed to fetch licenses, using bundled ones, error: {e}");
+            scaffold::bundled_licenses.iter()
+                .map(|l| (l.key.to_string(), l.spdx_id.to_string(), l.name.to_string()))
+                .collect()
+        },
+    };
+    let find_license = |license_raw: &str| licenses.iter()
+        .find(|(key, spdx_id, _)| key.eq_ignore_ascii_case(license_raw.trim()) || spdx_id.eq_ignore_ascii_case(license_raw.trim()))
+        .cloned();
+
+    let mut license_raw_opt = license_raw_opt.map(|l| l.to_string());
+    let (key, spdx_id, _) = loop {
+        let license_raw = match license_raw_opt.take() {
+            Some(l) => l,
+            None => match *cli_only {
+                true => return Ok(None),
+                false => prompt("License SPDX id (\"?\" to list, \"none\" to skip)", Some("none")).await.trim().to_string(),
+            },
+        };
+        if license_raw.eq_ignore_ascii_case("none") || license_raw.is_empty() {
+            return Ok(None);
+        } else if license_raw == "?" {
+            for (_, spdx_id, name) in &licenses {
+                aprintln!("  {spdx_id:<16} {name}");
+            }
+            continue;
+        }
+        match find_license(&license_raw) {
+            Some(l) => break l,
+            None => {
+                aprintln!("License \"{license_raw}\" not found");
+                if *cli_only {
+                    return Err(cli_only_error);
+                }
+            },
+        }
+    };
+    let body = match octocrab_license(octocrab_client, &key).await.map(|l| l.body) {
+        Ok(Some(body)) => body,
+        Ok(None) | Err(_) => match scaffold::bundled_license(&key) {
+            Some(l) => l.body.to_string(),
+            None => return_cmd_err!("Failed to fetch license {spdx_id} and there is no bundled copy"),
+        },
+    };
+    let year = chrono::Datelike::year(&chrono::Local::now());
+    Ok(Some((spdx_id, scaffold::license_fill(&body, year, holder))))
+}
+
+// Writes missing .gitignore & LICENSE, returns paths written
+async fn write_scaffold_files(
+    repo_path: &path::PathBuf,
+    gitignore_opt: &Option<(String, String)>,
+    license_opt: &Option<(String, String)>,
+) -> GhRsResult<Vec<path::PathBuf>> {
+    let mut paths_written = vec![];
+    let files = [
+        (".gitignore", gitignore_opt.as_ref().map(|(_, source)| source)),
+        ("LICENSE", license_opt.as_ref().map(|(_, text)| text)),
+    ];
+    for (file_name, content_opt) in files {
+        let content = match content_opt {
+            Some(c) => c,
+            None => continue,
+        };
+        let file_path = repo_path.join(file_name);
+        if file_path.exists().await {
+            aprintln!("{file_name} already exists, keeping it");
+            continue;
+        }
+        let _ = match fs::write(&file_path, content.as_bytes()).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to write {file_name}, error: {e}"),
+        };
+        paths_written.push(file_path);
+    }
+    Ok(paths_written)
+}
+
+async fn get_publish_remote_name(
+    repo: &git2::Repository,
+    remote_name_raw_opt: &Option<&str>,
+    remote_url: &str,
+    cli_only: &bool,
+) -> GhRsResult<String> {
+    let remote_name = remote_name_raw_opt.unwrap_or("origin").to_string();
+    let cli_only_error = GhRsError::CliOnly(format!("another remote name (\"{remote_name}\" already exists)"));
+
+    let existing_remote_url = match repo.find_remote(&remote_name) {
+        Err(e) => return Ok(remote_name),
+        Ok(r) => r.url().unwrap_or("").to_string(),
+    };
+    if github_git_urls_match(&existing_remote_url, remote_url) {
+        return Ok(remote_name);
+    }
+    aprintln!("Remote \"{remote_name}\" already exists and points to {existing_remote_url}");
+    if *cli_only {
+        return Err(cli_only_error);
+    }
+    // replacing drops remote tracking branches & branch upstream config
+    let replace_answer = prompt_bool(
+        format!("Replace \"{remote_name}\" with GitHub remote? Its tracking branches & upstreams will be removed").as_str(),
+        Some(false),
+    ).await;
+    if replace_answer {
+        return Ok(remote_name);
+    }
+    loop {
+        let remote_name_answer = prompt("GitHub remote name", Some("github")).await.trim().to_string();
+        if !git2::Remote::is_valid_name(&remote_name_answer) {
+            aprintln!("Invalid remote name format");
+            continue;
+        } else if repo.find_remote(&remote_name_answer).is_ok() {
+            aprintln!("Remote \"{remote_name_answer}\" already exists");
+            continue;
+        }
+        return Ok(remote_name_answer);
+    }
+}
+
+async fn get_is_public(public_raw: &bool, default_is_public: bool, cli_only: &bool) -> bool {
+    match *public_raw {
+        true => *public_raw,
+        false => match *cli_only {
+            true => false,
+            false => prompt_bool("Make it public?", Some(default_is_public)).await
+        }
+    }
+}
+
+fn get_readme_text(
+    username: &str,
+    repo_name: &str,
+    repo_description: &str,
+) -> String {
+    format!("# {repo_name}
+**{repo_description}**
+[GitHub](https://github.com/{username}/{repo_name})
+
+## Motivation
+Add motivation here
+
+## Installation
+`add_installation_command_here`
+
+## Usage
+Add usage & examples here
+
+## Development
+### TODO:
+- [x] Create project & repo
+- [ ] Fill out that TODO list
+- [ ] Fill out the Motivation section
+- [ ] Fill out the Installation section
+- [ ] Fill out the Usage section
+
+## Credits:
+- Local and remote (online) repo created from command line by [gh.rs]({gh_rs_github_url})
+
+
+",
+        gh_rs_github_url = get_gh_rs_github_url(),
+    )
+}
+
+
+// Template directory from local path, ~/.config/gh.rs/templates/<name> or git url (cloned to temp dir).
+// Returns (dir, is_temp)
+async fn get_template_dir(template_raw: &str) -> GhRsResult<(path::PathBuf, bool)> {
+    let template_raw = template_raw.trim();
+    let local_path = path!("{template_raw}");
+    if local_path.is_dir().await {
+        return Ok((local_path, false));
+    }
+    let is_name = !template_raw.is_empty() && !template_raw.contains(['/', '\\', ':']);
+    if is_name {
+        let config_path = path!("~/.config/gh.rs/templates/{template_raw}");
+        if config_path.is_dir().await {
+            return Ok((config_path, false));
+        }
+        return_cmd_err!(
+            "Template \"{template_raw}\" not found, put it in \"{config_path_display}\" or pass a path or git url",
+            config_path_display = path_display(&config_path)
+        );
+    }
+    let (template_url, template_name) = match resolve_import_source(template_raw) {
+        Some(s) => s,
+        None => return_cmd_err!("Template \"{template_raw}\" is neither a directory nor a git url"),
+    };
+    let clone_path = path::PathBuf::from(std::env::temp_dir()).join(format!("gh.rs-template-{template_name}"));
+    if clone_path.exists().await {
+        let _ = match fs::remove_dir_all(&clone_path).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to remove old template clone, error: {e}"),
+        };
+    }
+    aprintln!("Cloning template {template_url}...");
+    let _ = match git2_clone_with_credential_helpers(&template_url, &clone_path, &false) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to clone template {template_url}, error: {e}"),
+    };
+    Ok((clone_path, true))
+}
+
+// Template cloned from git url is temporary, local ones are kept
+async fn remove_template_dir(template_dir: &path::PathBuf, is_temp: &bool) {
+    if *is_temp {
+        let _ = fs::remove_dir_all(template_dir).await;
+    }
+}
+
+// Builtin values + custom manifest variables from --template-var name=value or prompted
+async fn get_template_values(
+    template_dir: &path::PathBuf,
+    template_vars: &Vec<&str>,
+    builtin_values: [(&str, &str); 4],
+    cli_only: &bool,
+) -> GhRsResult<HashMap<String, String>> {
+    let mut values: HashMap<String, String> = builtin_values.iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let mut values_raw: HashMap<&str, &str> = HashMap::new();
+    for template_var in template_vars {
+        match template_var.split_once('=') {
+            Some((name, value)) => values_raw.insert(name.trim(), value),
+            None => return_cmd_err!("Invalid --template-var \"{template_var}\", expected name=value"),
+        };
+    }
+    for variable in template::variables(template_dir.as_ref())? {
+        let value = match (values_raw.get(variable.name.as_str()), *cli_only) {
+            (Some(v), _) => v.to_string(),
+            (None, true) => match &variable.default {
+                Some(d) => d.clone(),
+                None => return Err(GhRsError::CliOnly(format!("template variable \"{name}\"", name = variable.name))),
+            },
+            (None, false) => prompt(&variable.prompt, variable.default.as_deref()).await.trim().to_string(),
+        };
+        values.insert(variable.name, value);
+    }
+    Ok(values)
+}
+
+// Generated repos are filled in the background, wait for commits before cloning
+async fn new_from_github_template(
+    octocrab_client: &octocrab::Octocrab,
+    token: &str,
+    user: &octocrab::models::Author,
+    template_path: (&str, &str),
+    owner: &str,
+    repo_name: &str,
+    repo_description: &str,
+    repo_public: &bool,
+    include_all_branches: &bool,
+    create_options: &OctocrabReposCreateOptions,
+    teams: &Vec<(String, String)>,
+    invites: &Vec<(String, String)>,
+    protect: &bool,
+) -> GhRsResult<()> {
+    let username = user.login.clone();
+    let (template_owner, template_repo_name) = template_path;
+    let gh_repo = match octocrab_repos_generate(
+        octocrab_client,
+        template_owner,
+        template_repo_name,
+        owner,
+        repo_public,
+        repo_name,
+        repo_description,
+        include_all_branches,
+    ).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to generate repo from {template_owner}/{template_repo_name}, error: {e}"),
+    };
+
+    let retry_timeout_duration = tokio::time::Duration::from_secs(2);
+    let retries_limit = 30;
+    let mut retries = 0;
+    aprintln!("Waiting for GitHub to generate {owner}/{repo_name}...");
+    loop {
+        let is_empty = match octocrab_repos_is_empty(octocrab_client, owner, repo_name).await {
+            Ok(e) => e,
+            Err(e) => return_cmd_err!("Failed to check {owner}/{repo_name} repo contents, error: {e}"),
+        };
+        if !is_empty { break; }
+        if retries >= retries_limit {
+            return_cmd_err!("Repo {owner}/{repo_name} is still empty, try cloning it later");
+        }
+        tokio::time::sleep(retry_timeout_duration).await;
+        retries += 1;
+    }
+    let _ = match octocrab_repos_update_options(octocrab_client, owner, repo_name, create_options).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to update repo settings, error: {e}"),
+    };
+    apply_teams(octocrab_client, owner, repo_name, teams).await?;
+    apply_invites(octocrab_client, owner, repo_name, invites).await?;
+
+    let ssh_private_key = match get_or_create_ssh_key(octocrab_client).await {
+        Ok(k) => k,
+        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
+    };
+    let repo_path = path!("./{repo_name}");
+    let remote_name = "origin";
+    let remote_url = format!("https://github.com/{owner}/{repo_name}.git");
+    let repo = match git2_clone(
+        &remote_url,
+        &repo_path,
+        &false,
+
+        username.as_str(),
+        token,
+        &ssh_private_key,
+    ) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to clone repo, error: {e}"),
+    };
+    if lfs::uses_lfs(&repo) {
+        match git2_lfs_checkout(&repo, &remote_url, username.as_str(), token).await {
+            Ok(count) => aprintln!("Checked out {count} LFS files"),
+            Err(e) => return_cmd_err!("Failed to check out LFS files, error: {e}"),
+        };
+    }
+
+    let branch_name = gh_repo.default_branch.clone().unwrap_or("main".to_string());
+    let _ = match git2_set_branch_upstream(&repo, &branch_name, remote_name) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to set \"{branch_name}\" branch upstream, error: {e}"),
+    };
+    if *protect {
+        apply_protection(octocrab_client, owner, repo_name, None, &branch_name, &get_protection_policy().await?).await?;
+    }
+
+    aprintln!(
+        "{hr}\n\nSUCCESS! Created {repo_name} repo from {template_owner}/{template_repo_name} template.\nHappy hacking & have a nice day :)",
+        hr = get_hr()
+    );
+    Ok(())
+}
+
+async fn run_new_cmd(
+    repo_name_raw_opt: &Option<&str>,
+    repo_description_raw_opt: &Option<&str>,
+    public_raw: &bool,
+    gitignore_raw_opt: &Option<&str>,
+    license_raw_opt: &Option<&str>,
+    template_raw_opt: &Option<&str>,
+    template_vars: &Vec<&str>,
+    from_template_raw_opt: &Option<&str>,
+    include_all_branches: &bool,
+    org_raw_opt: &Option<&str>,
+    teams_raw: &Vec<&str>,
+    invites_raw: &Vec<&str>,
+    create_options_raw: &RepoCreateOptionsRaw,
+    protect: &bool,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
+    let username = user.login.clone();
+    let teams = get_teams(teams_raw, org_raw_opt)?;
+    let invites = get_invites(invites_raw)?;
+    let owner = get_repo_owner(&octocrab_client, &username, org_raw_opt).await?;
+    let create_options = get_repo_create_options(create_options_raw, public_raw, org_raw_opt).await?;
+    if from_template_raw_opt.is_some() {
+        let (template_owner, template_repo_name, template_gh_repo) = get_external_path(&octocrab_client, from_template_raw_opt, cli_only).await?;
+        if template_gh_repo.is_template != Some(true) {
+            return_cmd_err!("Repo {template_owner}/{template_repo_name} is not a template repository");
+        }
+        let repo_name = get_repo_name(&octocrab_client, &owner, repo_name_raw_opt, Some(&template_repo_name), &true, &false, cli_only).await?;
+        let repo_description = get_repo_description(repo_description_raw_opt, template_gh_repo.description.as_deref(), cli_only).await?;
+        let repo_public = get_is_public_with_options(public_raw, &create_options, cli_only).await;
+        return new_from_github_template(
+            &octocrab_client,
+            &token,
+            &user,
+            (&template_owner, &template_repo_name),
+            &owner,
+            &repo_name,
+            &repo_description,
+            &repo_public,
+            include_all_branches,
+            &create_options,
+            &teams,
+            &invites,
+            protect,
+        ).await;
+    }
+    let repo_name = get_repo_name(&octocrab_client, &owner, repo_name_raw_opt, None, &true, &false, cli_only).await?;
+    let repo_description = get_repo_description(repo_description_raw_opt, None, cli_only).await?;
+    let repo_public = get_is_public_with_options(public_raw, &create_options, cli_only).await;
+    let sig = get_signature(&user).await?;
+    let gitignore_opt = get_gitignore_template(&octocrab_client, gitignore_raw_opt, None, cli_only).await?;
+    let license_opt = get_license(&octocrab_client, license_raw_opt, sig.name().unwrap_or(&username), cli_only).await?;
+    // every template value is known before anything is created on disk
+    let template_opt = match template_raw_opt {
+        Some(template_raw) => {
+            let (template_dir, is_temp) = get_template_dir(template_raw).await?;
+            let year = chrono::Datelike::year(&chrono::Local::now()).to_string();
+            let template_values_result = get_template_values(
+                &template_dir,
+                template_vars,
+                [("repo_name", &repo_name), ("username", &owner), ("description", &repo_description), ("year", &year)],
+                cli_only,
+            ).await;
+            match template_values_result {
+                Ok(template_values) => Some((template_dir, is_temp, template_values)),
+                Err(e) => {
+                    remove_template_dir(&template_dir, &is_temp).await;
+                    return Err(e);
+                },
+            }
+        },
+        None => None,
+    };
+
+    let repo_path = path!("./{repo_name}");
+    if let Err(e) = fs::create_dir(&repo_path).await {
+        if let Some((template_dir, is_temp, _)) = &template_opt {
+            remove_template_dir(template_dir, is_temp).await;
+        }
+        return_cmd_err!("Failed to create directory ./{repo_name}, error: {e}");
+    }
+    let branch_name = "main";
+    let repo = match git2::Repository::init_opts(
+        &repo_path, 
+        git2::RepositoryInitOptions::new()
+            .initial_head("main")
+    ) {
+        Ok(r) => r,
+        Err(e) => {
+            if let Some((template_dir, is_temp, _)) = &template_opt {
+                remove_template_dir(template_dir, is_temp).await;
+            }
+            return_cmd_err!(
+                "Failed to init repo in \"{repo_path_display}\", error: {e}",
+                repo_path_display = path_display(&repo_path)
+            );
+        },
+    };
+
+    if let Some((template_dir, is_temp, template_values)) = &template_opt {
+        let render_result = template::render_dir(template_dir.as_ref(), repo_path.as_ref(), template_values);
+        remove_template_dir(template_dir, is_temp).await;
+        let files_count = match render_result {
+            Ok(c) => c,
+            Err(e) => {
+                // half rendered repo dir would block the next run
+                drop(repo);
+                let _ = fs::remove_dir_all(&repo_path).await;
+                return_cmd_err!("Failed to render template, error: {e}");
+            },
+        };
+        aprintln!("Rendered {files_count} template files");
+    }
+    // templates usually bring their own README
+    let readme_path = repo_path.join("README.md");
+    if !readme_path.exists().await {
+        let _ = match fs::write(&readme_path, get_readme_text(&owner, &repo_name, &repo_description).as_bytes()).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to write README.md, error: {e}"),
+        };
+    }
+    write_scaffold_files(&repo_path, &gitignore_opt, &license_opt).await?;
+
+    let _ = match git2_add_all_and_commit(
+        &repo, 
+        &sig, 
+        ["."], 
+        "Initial commit [gh.rs]",
+    ).await {
+        Err(e) => return_cmd_err!("Failed to commit, error: {e}"),
+        Ok(r) => r,
+    };
+    let ssh_private_key = match get_or_create_ssh_key(&octocrab_client).await {
+        Ok(k) => k,
+        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
+    };
+    // resolved org login, not the raw --org value
+    let org_opt = (owner != username).then_some(owner.as_str());
+    let gh_repo: octocrab::models::Repository = match octocrab_repos_create(
+        &octocrab_client,
+        &org_opt,
+        &repo_public,
+        &repo_name,
+        &repo_description,
+        &create_options,
+    ).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to create repo, error: {e}"),
+    };
+    let _ = match octocrab_repos_update_security(&octocrab_client, &owner, &repo_name, &create_options).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to update security settings, error: {e}"),
+    };
+    apply_teams(&octocrab_client, &owner, &repo_name, &teams).await?;
+    apply_invites(&octocrab_client, &owner, &repo_name, &invites).await?;
+    
+    let remote_name = "origin";
+    let remote_url = format!("https://github.com/{owner}/{repo_name}.git");
+    let _ = match git2_push(
+        &repo,
+        &remote_name,
+        &remote_url,
+        &false,
+
+        user.login.clone().as_str(), 
+        &token, 
+        &ssh_private_key,
+    ) {
+        Err(e) => return_cmd_err!("Failed to push, error: {e}"),
+        Ok(r) => r,
+    };
+    
+    let _ = match git2_set_branch_upstream(&repo, branch_name, remote_name) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to set \"{branch_name}\" branch upstream, error: {e}"),
+    };
+    // after the push, required reviews would reject it
+    if *protect {
+        apply_protection(&octocrab_client, &owner, &repo_name, None, branch_name, &get_protection_policy().await?).await?;
+    }
+
+    aprintln!("{hr}\n\nSUCCESS! Created {repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
+
+
+async fn publish_commit(
+    repo: &git2::Repository,
+    signature: &git2::Signature<'_>,
+    message: &str,
+    interactive: &bool,
+    secret_rules: &[secrets::SecretRule],
+    allow_secrets: &bool,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let selected_paths_opt = match *interactive {
+        true => Some(get_files_selection(repo, cli_only).await?),
+        false => None,
+    };
+    let secret_findings = match &selected_paths_opt {
+        Some(selected_paths) => secrets::scan_files(repo, secret_rules, selected_paths),
+        None => secrets::scan_worktree(repo, secret_rules),
+    };
+    let secret_findings = match secret_findings {
+        Ok(f) => f,
+        Err(e) => return_cmd_err!("Failed to scan files for secrets, error: {e}"),
+    };
+    check_secret_findings(&secret_findings, allow_secrets).await?;
+    let commit_result = match &selected_paths_opt {
+        Some(selected_paths) => {
+            if selected_paths.is_empty() {
+                return_cmd_err!("No files selected to commit");
+            }
+            git2_commit_paths(repo, signature, selected_paths, message)
+        },
+        None => git2_add_all_and_commit(repo, signature, ["."], message).await,
+    };
+    let _ = match commit_result {
+        Err(e) => return_cmd_err!("Failed to commit, error: {e}"),
+        Ok(r) => r,
+    };
+    Ok(())
+}
+
+async fn run_publish_cmd(
+    repo_name_raw_opt: &Option<&str>,
+    repo_description_raw_opt: &Option<&str>,
+    repo_topics_raw_opt: &Option<&str>,
+    public_raw: &bool,
+    remote_name_raw_opt: &Option<&str>,
+    all_branches: &bool,
+    tags: &bool,
+    interactive: &bool,
+    allow_secrets: &bool,
+    strip_paths: &Vec<&str>,
+    strip_blobs_bigger_than_raw_opt: &Option<&str>,
+    mailmap_path_raw_opt: &Option<&str>,
+    gitignore_raw_opt: &Option<&str>,
+    license_raw_opt: &Option<&str>,
+    org_raw_opt: &Option<&str>,
+    teams_raw: &Vec<&str>,
+    create_options_raw: &RepoCreateOptionsRaw,
+    protect: &bool,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
+    let username = user.login.clone();
+    let teams = get_teams(teams_raw, org_raw_opt)?;
+    let owner = get_repo_owner(&octocrab_client, &username, org_raw_opt).await?;
+    let mut create_options = get_repo_create_options(create_options_raw, public_raw, org_raw_opt).await?;
+    let rewrite_options = &get_rewrite_options(strip_paths, strip_blobs_bigger_than_raw_opt, mailmap_path_raw_opt)?;
+    
+    let repo_path = path!("./").canonicalize().await.expect("must be able to canonicalize");
+    let repo_path_sync: std::path::PathBuf = repo_path.clone().into();
+    let project_metadata = project::metadata(&repo_path_sync);
+    // --homepage, then project manifest, then profile
+    create_options.homepage = create_options_raw.homepage.clone()
+        .or(project_metadata.homepage.clone())
+        .or(create_options.homepage);
+    // manifest name is usually nicer than directory name, both may need slugifying
+    let repo_name_default = project_metadata.name.clone()
+        .or_else(|| repo_path_sync.file_name().map(|n| n.to_string_lossy().to_string()))
+        .map(|n| project::slugify(&n))
+        .filter(|n| re_repo_name().is_match(n));
+    let repo_name_raw_opt = match repo_name_raw_opt {
+        Some(n) => Some(*n),
+        None if *cli_only => repo_name_default.as_deref(),
+        None => None,
+    };
+    let repo_name = get_repo_name(&octocrab_client, &owner, &repo_name_raw_opt, repo_name_default.as_deref(), &false, &true, cli_only).await?;
+    let repo_description = get_repo_description(repo_description_raw_opt, project_metadata.description.as_deref(), cli_only).await?;
+    let repo_public = get_is_public_with_options(public_raw, &create_options, cli_only).await;
+    let repo_topics = get_repo_topics(repo_topics_raw_opt, &project::suggested_topics(&project_metadata), cli_only).await?;
+    
+    let repo_name = repo_name.as_str();
+    let repo = match repo_path.join(".git").is_dir().await {
+        true => match git2::Repository::open(&repo_path) {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!(
+                "Failed to open repo from \"{repo_path_display}\", error: {e}",
+                repo_path_display = path_display(&repo_path)
+            ),
+        },
+        false => match git2::Repository::init_opts(
+            &repo_path, 
+            git2::RepositoryInitOptions::new()
+                .initial_head("main")
+        ) {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!(
+                "Failed to init repo in \"{repo_path_display}\", error: {e}",
+                repo_path_display = path_display(&repo_path)
+            ),
+        },
+    };
+
+    let secret_rules = get_secret_rules().await?;
+    let sig = get_signature(&user).await?;
+    // only asked for when missing, explicit flags still go through (existing files are kept)
+    let gitignore_opt = match gitignore_raw_opt.is_none() && repo_path.join(".gitignore").exists().await {
+        true => None,
+        false => {
+            let gitignore_default = project_metadata.language.as_deref().and_then(scaffold::gitignore_for_language);
+            get_gitignore_template(&octocrab_client, gitignore_raw_opt, gitignore_default, cli_only).await?
+        },
+    };
+    let mut has_license = false;
+    for license_file_name in ["LICENSE", "LICENSE.md", "LICENSE.txt", "COPYING"] {
+        has_license |= repo_path.join(license_file_name).exists().await;
+    }
+    let license_opt = match license_raw_opt.is_none() && has_license {
+        true => None,
+        false => get_license(&octocrab_client, license_raw_opt, sig.name().unwrap_or(&username), cli_only).await?,
+    };
+    let has_commits = repo.head().ok().and_then(|h| h.peel_to_commit().ok()).is_some();
+    if !has_commits {
+        write_scaffold_files(&repo_path, &gitignore_opt, &license_opt).await?;
+        let readme_path = repo_path.join("README.md");
+        if !readme_path.exists().await {
+            let _ = match fs::write(&readme_path, get_readme_text(&owner, &repo_name, &repo_description).as_bytes()).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to write README.md, error: {e}"),
+            };
         }
-        for reference_name in references_to_remove {
-            repo.find_reference(reference_name.as_str())?.delete()?;
+        publish_commit(&repo, &sig, "Initial commit [gh.rs]", interactive, &secret_rules, allow_secrets, cli_only).await?;
+    } else {
+        if repo.head_detached()? {
+            return_cmd_err!("HEAD is detached, checkout a branch to publish first");
+        }
+        let scaffold_paths = write_scaffold_files(&repo_path, &gitignore_opt, &license_opt).await?;
+        if !scaffold_paths.is_empty() {
+            let scaffold_file_names: Vec<String> = scaffold_paths.iter()
+                .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .collect();
+            let _ = match git2_commit_paths(
+                &repo,
+                &sig,
+                &scaffold_file_names,
+                &format!("Add {files} [gh.rs]", files = scaffold_file_names.join(" & ")),
+            ) {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to commit {files}, error: {e}", files = scaffold_file_names.join(" & ")),
+            };
+        }
+        let is_dirty = match git2_is_dirty(&repo) {
+            Ok(d) => d,
+            Err(e) => return_cmd_err!("Failed to get working tree status, error: {e}"),
+        };
+        // existing history is published as-is, uncommitted changes only on request
+        if is_dirty {
+            if *cli_only {
+                return_cmd_err!("Working tree has uncommitted changes, commit or stash them before publishing");
+            }
+            aprintln!("Working tree has uncommitted changes");
+            match prompt_bool("Commit them before publishing?", Some(true)).await {
+                true => {
+                    let commit_message = prompt("Commit message", Some("Publish [gh.rs]")).await.trim().to_string();
+                    publish_commit(&repo, &sig, &commit_message, interactive, &secret_rules, allow_secrets, cli_only).await?;
+                },
+                false => aprintln!("Uncommitted changes are left out of the published repo"),
+            }
+        }
+    }
+    let branch_name = match git2_default_branch_name(&repo) {
+        Ok(n) => n,
+        Err(e) => return_cmd_err!("Failed to get current branch name, error: {e}"),
+    };
+    let refspecs = match git2_refspecs(&repo, &branch_name, all_branches, tags) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to list branches & tags to push, error: {e}"),
+    };
+    let refspecs_refs: Vec<&str> = refspecs.iter()
+        .map(|r| r.split_once(':').map(|(src, _)| src).unwrap_or(r))
+        .collect();
+    apply_history_rewrite(&repo, &refspecs_refs, rewrite_options).await?;
+    let secret_findings = match secrets::scan_history(&repo, &secret_rules, &refspecs_refs) {
+        Ok(f) => f,
+        Err(e) => return_cmd_err!("Failed to scan history for secrets, error: {e}"),
+    };
+    check_secret_findings(&secret_findings, allow_secrets).await?;
+    check_push_size(&repo, &refspecs_refs, cli_only).await?;
+
+    let ssh_private_key = match get_or_create_ssh_key(&octocrab_client).await {
+        Ok(k) => k,
+        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
+    };
+    let remote_url = format!("https://github.com/{owner}/{repo_name}.git");
+    let remote_name = get_publish_remote_name(&repo, remote_name_raw_opt, &remote_url, cli_only).await?;
+    let remote_name = remote_name.as_str();
+
+    let gh_repo: octocrab::models::Repository = match octocrab_client.repos(owner.clone(), repo_name).get().await {
+        Ok(existing_gh_repo) => {
+            let is_empty = match octocrab_repos_is_empty(&octocrab_client, &owner, repo_name).await {
+                Ok(e) => e,
+                Err(e) => return_cmd_err!("Failed to check {owner}/{repo_name} repo contents, error: {e}"),
+            };
+            if !is_empty {
+                return_cmd_err!("Repo {owner}/{repo_name} already exists and is not empty");
+            }
+            aprintln!("Repo {owner}/{repo_name} already exists and is empty");
+            if *cli_only {
+                return Err(GhRsError::CliOnly("confirmation to push into existing repo".to_string()));
+            }
+            if !prompt_bool("Push into it?", Some(true)).await {
+                return_cmd_err!("Repo {owner}/{repo_name} already exists");
+            }
+            let _ = match octocrab_repos_update_options(&octocrab_client, &owner, repo_name, &create_options).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to update repo settings, error: {e}"),
+            };
+            match octocrab_repos_update(
+                &octocrab_client,
+                &owner,
+                repo_name,
+                &serde_json::json!({ "description": repo_description }),
+            ).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to update repo, error: {e}"),
+            }
+        },
+        Err(e) => {
+            let org_opt = (owner != username).then_some(owner.as_str());
+            let gh_repo = match octocrab_repos_create(
+                &octocrab_client,
+                &org_opt,
+                &repo_public,
+                &repo_name,
+                &repo_description,
+                &create_options,
+            ).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to create repo, error: {e}"),
+            };
+            let _ = match octocrab_repos_update_security(&octocrab_client, &owner, repo_name, &create_options).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to update security settings, error: {e}"),
+            };
+            gh_repo
+        },
+    };
+    apply_teams(&octocrab_client, &owner, repo_name, &teams).await?;
+    
+    if lfs::uses_lfs(&repo) {
+        let mut lfs_ref_globs = vec![format!("heads/{branch_name}")];
+        if *all_branches {
+            lfs_ref_globs.push("heads/*".to_string());
+        }
+        if *tags {
+            lfs_ref_globs.push("tags/*".to_string());
+        }
+        match git2_lfs_transfer(
+            &repo,
+            None,
+            &lfs::endpoint_for_remote_url(&remote_url).expect("github url"),
+            &lfs_ref_globs.iter().map(|g| g.as_str()).collect::<Vec<&str>>(),
+
+            user.login.clone().as_str(), 
+            &token,
+        ).await {
+            Ok(count) => aprintln!("Uploaded {count} LFS objects"),
+            Err(e) => return_cmd_err!("Failed to upload LFS objects, error: {e}"),
+        };
+    }
+    let _ = match git2_push_refspecs(
+        &repo,
+        remote_name,
+        &remote_url,
+        &refspecs,
+
+        user.login.clone().as_str(), 
+        &token, 
+        &ssh_private_key,
+    ) {
+        Err(e) => return_cmd_err!("Failed to push, error: {e}"),
+        Ok(r) => r,
+    };
+    // with several branches pushed GitHub may pick another one as default
+    if *all_branches {
+        let _ = match octocrab_repos_update(
+            &octocrab_client,
+            &owner,
+            repo_name,
+            &serde_json::json!({ "default_branch": branch_name }),
+        ).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to set \"{branch_name}\" as default branch, error: {e}"),
+        };
+    }
+    let mut upstream_branch_names = vec![branch_name.clone()];
+    if *all_branches {
+        for (branch, _) in repo.branches(Some(git2::BranchType::Local))?.flatten() {
+            if let Ok(Some(name)) = branch.name() {
+                if name != branch_name {
+                    upstream_branch_names.push(name.to_string());
+                }
+            }
+        }
+    }
+    for upstream_branch_name in &upstream_branch_names {
+        let _ = match git2_set_branch_upstream(&repo, upstream_branch_name, remote_name) {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to set \"{upstream_branch_name}\" branch upstream, error: {e}"),
+        };
+    }
+
+    if *protect {
+        apply_protection(&octocrab_client, &owner, repo_name, None, &branch_name, &get_protection_policy().await?).await?;
+    }
+
+    if !repo_topics.is_empty() {
+        let _ = match octocrab_repos_replace_topics(&octocrab_client, &owner, repo_name, &repo_topics).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to set topics, error: {e}"),
+        };
+    }
+
+    aprintln!("{hr}\n\nSUCCESS! Published {repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
+
+async fn run_clone_cmd(
+    external_path_raw_opt: &Option<&str>,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
+    let username = user.login.clone();
+    let (external_username, external_repo_name, external_gh_repo) = get_external_path(&octocrab_client, external_path_raw_opt, cli_only).await?;
+    
+    let path = path!("./{external_repo_name}");
+    let _ = match fs::create_dir(path.clone()).await {
+        Err(e) => return_cmd_err!("Failed to create directory ./{external_repo_name}, error: {e}"),
+        Ok(r) => r,
+    };
+    
+    let ssh_private_key = match get_or_create_ssh_key(&octocrab_client).await {
+        Ok(k) => k,
+        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
+    };
+    
+    let repo_clone_url = format!("https://github.com/{external_username}/{external_repo_name}.git");
+    let repo = match git2_clone(
+        &repo_clone_url,
+        &path,
+        &false,
+
+        user.login.clone().as_str(), 
+        &token, 
+        &ssh_private_key,
+    ) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to clone repo, error: {e}"),
+    };
+    if lfs::uses_lfs(&repo) {
+        match git2_lfs_checkout(&repo, &repo_clone_url, user.login.clone().as_str(), &token).await {
+            Ok(count) => aprintln!("Checked out {count} LFS files"),
+            Err(e) => return_cmd_err!("Failed to check out LFS files, error: {e}"),
+        };
+    }
+    
+    aprintln!("{hr}\n\nSUCCESS! Cloned {external_repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+struct ForkCopyOptions {
+    labels: bool,
+    milestones: bool,
+    releases: bool,
+    wiki: bool,
+}
+
+async fn fork_mirror(
+    octocrab_client: &octocrab::Octocrab,
+    token: &str,
+    user: &octocrab::models::Author,
+    ssh_private_key: &ssh_key::PrivateKey,
+    external_username: &str,
+    external_repo_name: &str,
+    external_gh_repo: &octocrab::models::Repository,
+    owner: &str,
+    repo_name: &str,
+    repo_public: &bool,
+    mirror_path: &path::PathBuf,
+    copy_options: &ForkCopyOptions,
+    create_options: &OctocrabReposCreateOptions,
+    rewrite_options: &rewrite::RewriteOptions,
+    cli_only: &bool,
+) -> GhRsResult<git2::Repository> {
+    // repos of other owners are created in org
+    let org_opt = (owner != user.login).then_some(owner);
+    let external_url = format!("https://github.com/{external_username}/{external_repo_name}.git");
+    let external_repo = match git2_clone(
+        &external_url,
+        mirror_path,
+        &true,
+
+        user.login.clone().as_str(), 
+        token, 
+        ssh_private_key,
+    ) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to clone repo, error: {e}"),
+    };
+    let push_ref_names = git2_push_ref_names(&external_repo)?;
+    let push_ref_names: Vec<&str> = push_ref_names.iter().map(|r| r.as_str()).collect();
+    apply_history_rewrite(&external_repo, &push_ref_names, rewrite_options).await?;
+    check_push_size(&external_repo, &push_ref_names, cli_only).await?;
+    
+    let gh_repo: octocrab::models::Repository = match octocrab_repos_create(
+        octocrab_client,
+        &org_opt,
+        repo_public,
+        repo_name,
+        external_gh_repo.description.clone().unwrap_or("".to_string()).as_str(),
+        &OctocrabReposCreateOptions {
+            homepage: create_options.homepage.clone().or(external_gh_repo.homepage.clone()),
+            has_issues: create_options.has_issues.or(external_gh_repo.has_issues),
+            has_projects: create_options.has_projects.or(external_gh_repo.has_projects),
+            has_wiki: create_options.has_wiki.or(external_gh_repo.has_wiki),
+            ..create_options.clone()
+        },
+    ).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to create repo, error: {e}"),
+    };
+    let _ = match octocrab_repos_update_security(octocrab_client, owner, repo_name, create_options).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to update security settings, error: {e}"),
+    };
+    
+    let remote_name = "origin";
+    let remote_url = format!("https://github.com/{owner}/{repo_name}.git");
+    if lfs::uses_lfs(&external_repo) {
+        match git2_lfs_transfer(
+            &external_repo,
+            lfs::endpoint_for_source(&external_repo, &external_url).as_deref(),
+            &lfs::endpoint_for_remote_url(&remote_url).expect("github url"),
+            &["*"],
+
+            user.login.clone().as_str(), 
+            token,
+        ).await {
+            Ok(count) => aprintln!("Uploaded {count} LFS objects"),
+            Err(e) => return_cmd_err!("Failed to copy LFS objects, error: {e}"),
+        };
+    }
+    let _ = match git2_push(
+        &external_repo,
+        remote_name,
+        &remote_url,
+        &true,
+
+        user.login.clone().as_str(), 
+        token, 
+        ssh_private_key,
+    ) {
+        Err(e) => return_cmd_err!("Failed to push, error: {e}"),
+        Ok(r) => r,
+    };
+
+    // mirror push order decides default branch, set it explicitly
+    if let Some(external_default_branch_name) = &external_gh_repo.default_branch {
+        let _ = match octocrab_repos_update(
+            octocrab_client,
+            owner,
+            repo_name,
+            &serde_json::json!({ "default_branch": external_default_branch_name }),
+        ).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to set \"{external_default_branch_name}\" as default branch, error: {e}"),
+        };
+    }
+    if let Some(external_topics) = &external_gh_repo.topics {
+        if !external_topics.is_empty() {
+            let _ = match octocrab_repos_replace_topics(octocrab_client, owner, repo_name, external_topics).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to copy topics, error: {e}"),
+            };
         }
-        Ok(())
     }
+    if copy_options.labels {
+        match octocrab_labels_copy(octocrab_client, external_username, external_repo_name, owner, repo_name).await {
+            Ok(count) => aprintln!("Copied {count} labels"),
+            Err(e) => return_cmd_err!("Failed to copy labels, error: {e}"),
+        };
+    }
+    if copy_options.milestones {
+        match octocrab_milestones_copy(octocrab_client, external_username, external_repo_name, owner, repo_name).await {
+            Ok(count) => aprintln!("Copied {count} milestones"),
+            Err(e) => return_cmd_err!("Failed to copy milestones, error: {e}"),
+        };
+    }
+    if copy_options.releases {
+        let http_client = reqwest_client(token)?;
+        match octocrab_releases_copy(octocrab_client, &http_client, external_username, external_repo_name, owner, repo_name).await {
+            Ok(count) => aprintln!("Copied {count} releases"),
+            Err(e) => return_cmd_err!("Failed to copy releases, error: {e}"),
+        };
+    }
+    if copy_options.wiki {
+        let external_wiki_url = format!("https://github.com/{external_username}/{external_repo_name}.wiki.git");
+        let external_has_wiki = external_gh_repo.has_wiki.unwrap_or(false) && git2_remote_is_reachable(
+            &external_wiki_url,
 
-    fn remove_remote_tracking(
-        repo: &git2::Repository,
-        remote_name: &str,
-    ) -> Result<(), git2::Error> {
-        let remote = repo.find_remote(remote_name)?;
-        for refspec in remote.refspecs() {
-            remove_refs(repo, &refspec)?;
+            user.login.clone().as_str(), 
+            token, 
+            ssh_private_key,
+        );
+        if external_has_wiki {
+            let _ = match octocrab_repos_update(
+                octocrab_client,
+                owner,
+                repo_name,
+                &serde_json::json!({ "has_wiki": true }),
+            ).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to enable wiki, error: {e}"),
+            };
+
+            let wiki_path = path!("{mirror_path_display}.wiki", mirror_path_display = path_display(mirror_path));
+            let external_wiki_repo = match git2_clone(
+                &external_wiki_url,
+                &wiki_path,
+                &true,
+
+                user.login.clone().as_str(), 
+                token, 
+                ssh_private_key,
+            ) {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to clone wiki, error: {e}"),
+            };
+
+            // GitHub creates wiki git repo only after the first page is saved from web UI
+            let wiki_remote_url = format!("https://github.com/{owner}/{repo_name}.wiki.git");
+            loop {
+                match git2_push(
+                    &external_wiki_repo,
+                    remote_name,
+                    &wiki_remote_url,
+                    &true,
+
+                    user.login.clone().as_str(), 
+                    token, 
+                    ssh_private_key,
+                ) {
+                    Ok(_) => {
+                        aprintln!("Copied wiki");
+                        break;
+                    },
+                    Err(e) => {
+                        aprintln!("Failed to push wiki, error: {e}");
+                        if *cli_only {
+                            let _ = fs::remove_dir_all(&wiki_path).await;
+                            return Err(GhRsError::CliOnly("wiki first page creation".to_string()));
+                        }
+                        aprintln!("Create any first page at https://github.com/{owner}/{repo_name}/wiki/_new, it will be overwritten");
+                        if !prompt_bool("Retry wiki push?", Some(true)).await {
+                            break;
+                        }
+                    },
+                }
+            }
+            let _ = match fs::remove_dir_all(&wiki_path).await {
+                Err(e) => return_cmd_err!(
+                    "Failed to remove directory \"{path_display}\", error: {e}", 
+                    path_display = path_display(&wiki_path)
+                ),
+                Ok(r) => r,
+            };
+        } else {
+            aprintln!("External repo {external_username}/{external_repo_name} has no wiki, skipping");
         }
-        Ok(())
     }
-    
-    fn remove_config_remote_section(
-        repo: &git2::Repository,
-        remote_name: &str,
-    ) -> Result<(), git2::Error> {
-        // this fn fails in libgit2, bcuz of git_config_delete_entry() used, not remove_multivar()
-        let config = repo.config()?;
-        let mut entries = config.entries(Some(format!(r"remote.{remote_name}\..*").as_str()))?;
-        let mut entries_to_remove = HashSet::new();
-        while let Some(entry) = entries.next() {
-            let entry = entry?;
-            let entry_name = entry.name().expect("must be some").to_owned();
-            entries_to_remove.insert(entry_name);
-        }
-        let mut config = repo.config()?;
-        for entry in entries_to_remove {
-            config.remove_multivar(entry.as_str(), r".*")?; // fix is here
+    Ok(external_repo)
+}
+
+fn resolve_github_git_url(git_url: &str) -> Option<(&str, &str)> {
+    for prefix in [
+        "https://github.com/", 
+        "http://github.com/", 
+        "ssh://git@github.com/", 
+        "git://github.com/", 
+        "git@github.com:",
+    ] {
+        if let Some(path) = git_url.strip_prefix(prefix) {
+            return resolve_github_path(path);
         }
-        Ok(())
     }
-
-    remove_branch_config_related_entries(repo, remote_name)?;
-    remove_remote_tracking(repo, remote_name)?;
-    
-    // this fn fails in libgit2, bcuz of git_config_delete_entry() used, not remove_multivar()
-    remove_config_remote_section(repo, remote_name)?;
-    
-    Ok(())
+    None
 }
 
-fn git2_remote_recreate<'repo>(
-    repo: &'repo git2::Repository,
-    name: &str,
-    url: &str,
-) -> Result<git2::Remote<'repo>, git2::Error> {
-    if let Ok(r) = repo.find_remote(name) {
-        git2_remote_delete(repo, name)?;
+// Same GitHub repo whatever the transport, names are case insensitive
+fn github_git_urls_match(git_url: &str, other_git_url: &str) -> bool {
+    match (resolve_github_git_url(git_url), resolve_github_git_url(other_git_url)) {
+        (Some((owner, repo_name)), Some((other_owner, other_repo_name))) =>
+            owner.eq_ignore_ascii_case(other_owner) && repo_name.eq_ignore_ascii_case(other_repo_name),
+        _ => git_url == other_git_url,
     }
-    repo.remote(name, url)
 }
 
-fn git2_generate_refspecs_from_globbed(
-    repo: &git2::Repository,
-    refspecs_globbed: HashSet<String>,
-) -> GhRsResult<HashSet<String>> {
-    let refspecs = HashSet::new();
-    for rg in refspecs_globbed {
-        let mut rg = rg;
-        let is_plus = rg.starts_with("+");
-        if is_plus {
-            rg = rg.strip_prefix("+").unwrap_or(&rg).to_owned();
-        }
-        if !rg.starts_with("refs") {
-            return_cmd_err!("Refspec must start with \"refs\", here: {rg}");
-        }
-        let got_git_path = repo.path();
-        
+async fn fork_submodules(
+    octocrab_client: &octocrab::Octocrab,
+    token: &str,
+    user: &octocrab::models::Author,
+    ssh_private_key: &ssh_key::PrivateKey,
+    signature: &git2::Signature<'_>,
+    repo: &mut git2::Repository,
+    remote_url: &str,
+    superproject_external_url: &str,
+    owner: &str,
+    cli_only: &bool,
+    forked_urls: &mut Vec<(String, String)>,
+) -> GhRsResult<()> {
+    let submodules: Vec<(Option<String>, Option<String>)> = repo.submodules()?.iter()
+        .map(|sm| (sm.name().map(|n| n.to_string()), sm.url().map(|u| u.to_string())))
+        .collect();
+    // relative submodule urls are resolved against superproject url
+    let superproject_base_url = format!("{}/", superproject_external_url.trim_end_matches(".git"));
+    let mut gitmodules_rewritten = false;
+    for submodule in submodules {
+        let (submodule_name, submodule_url) = match submodule {
+            (Some(n), Some(u)) => (n, u),
+            _ => continue,
+        };
+        let submodule_url_abs = match submodule_url.starts_with("./") || submodule_url.starts_with("../") {
+            false => submodule_url.clone(),
+            true => match url::Url::parse(&superproject_base_url).and_then(|b| b.join(&submodule_url)) {
+                Ok(u) => u.to_string(),
+                Err(e) => return_cmd_err!("Failed to resolve submodule {submodule_name} url {submodule_url}, error: {e}"),
+            },
+        };
+        let (external_username, external_repo_name) = match resolve_github_git_url(&submodule_url_abs) {
+            Some((username_str, repo_name_str)) => (username_str.to_string(), repo_name_str.to_string()),
+            None => {
+                aprintln!("Submodule {submodule_name} ({submodule_url}) is not hosted on GitHub, skipping");
+                continue;
+            },
+        };
+
+        let forked_url = match forked_urls.iter().find(|(original, _)| original == &submodule_url_abs) {
+            Some((_, forked)) => forked.clone(),
+            None => {
+                if !*cli_only && !prompt_bool(format!("Fork submodule {submodule_name} ({submodule_url_abs})?").as_str(), Some(true)).await {
+                    continue;
+                }
+                let external_gh_repo = match octocrab_client.repos(external_username.clone(), external_repo_name.clone()).get().await {
+                    Ok(r) => r,
+                    Err(e) => return_cmd_err!("Repo {external_username}/{external_repo_name} of submodule {submodule_name} is unavailable, error: {e}"),
+                };
+                let repo_name = get_repo_name(
+                    octocrab_client, 
+                    owner, 
+                    &Some(external_repo_name.as_str()), 
+                    Some(&external_repo_name), 
+                    &false,
+                    &false,
+                    cli_only
+                ).await?;
+
+                let mirror_path = path::PathBuf::from(std::env::temp_dir()).join(format!("gh.rs-{repo_name}.git"));
+                // submodule forks are always private, whatever the superproject visibility
+                let mirror_result = fork_mirror(
+                    octocrab_client,
+                    token,
+                    user,
+                    ssh_private_key,
+                    &external_username,
+                    &external_repo_name,
+                    &external_gh_repo,
+                    owner,
+                    &repo_name,
+                    &false,
+                    &mirror_path,
+                    &ForkCopyOptions::default(),
+                    &OctocrabReposCreateOptions::default(),
+                    &rewrite::RewriteOptions::default(),
+                    cli_only,
+                ).await;
+                let mirror_head_result = mirror_result.and_then(|mirror_repo| {
+                    let mirror_head_commit = mirror_repo.head()?.peel_to_commit()?;
+                    let has_gitmodules = mirror_head_commit.tree()?.get_name(".gitmodules").is_some();
+                    Ok((mirror_head_commit.id(), has_gitmodules))
+                });
+                let (last_commit_id, has_gitmodules) = match mirror_head_result {
+                    Ok(r) => r,
+                    Err(e) => {
+                        let _ = fs::remove_dir_all(&mirror_path).await;
+                        return Err(e);
+                    },
+                };
+                let _ = match fs::remove_dir_all(&mirror_path).await {
+                    Err(e) => return_cmd_err!(
+                        "Failed to remove directory \"{path_display}\", error: {e}", 
+                        path_display = path_display(&mirror_path)
+                    ),
+                    Ok(r) => r,
+                };
+
+                let forked_url = format!("https://github.com/{owner}/{repo_name}.git");
+                forked_urls.push((submodule_url_abs.clone(), forked_url.clone()));
+
+                if has_gitmodules {
+                    let work_path = path::PathBuf::from(std::env::temp_dir()).join(format!("gh.rs-{repo_name}"));
+                    let mut submodule_repo = match git2_clone(
+                        &forked_url,
+                        &work_path,
+                        &false,
+
+                        user.login.clone().as_str(), 
+                        token, 
+                        ssh_private_key,
+                    ) {
+                        Ok(r) => r,
+                        Err(e) => return_cmd_err!("Failed to clone repo, error: {e}"),
+                    };
+                    let _ = match git2_fetch_until_commit(
+                        &submodule_repo,
+                        "origin",
+                        &forked_url,
+                        &last_commit_id,
+                        &tokio::time::Duration::new(0, 500_000_000),
+                        10,
+
+                        user.login.clone().as_str(), 
+                        token, 
+                        ssh_private_key,
+                    ).await {
+                        Ok(r) => r,
+                        Err(e) => return_cmd_err!("Failed to fetch remote until latest commit, error: {e}"),
+                    };
+                    Box::pin(fork_submodules(
+                        octocrab_client,
+                        token,
+                        user,
+                        ssh_private_key,
+                        signature,
+                        &mut submodule_repo,
+                        &forked_url,
+                        &submodule_url_abs,
+                        owner,
+                        cli_only,
+                        forked_urls,
+                    )).await?;
+                    let _ = match fs::remove_dir_all(&work_path).await {
+                        Err(e) => return_cmd_err!(
+                            "Failed to remove directory \"{path_display}\", error: {e}", 
+                            path_display = path_display(&work_path)
+                        ),
+                        Ok(r) => r,
+                    };
+                }
+                forked_url
+            },
+        };
+        repo.submodule_set_url(&submodule_name, &forked_url)?;
+        gitmodules_rewritten = true;
     }
-    Ok(refspecs)
-}
 
-fn git2_default_branch_name(
-    repo: &git2::Repository,
-) -> GhRsResult<String> {
-    let head = repo.head()?;
-    let head_name = match head.name() {
-        Some(n) => n, 
-        None => return_cmd_err!("Failed to encode head name as utf8")
-    };
-    let branch_name = head_name.strip_prefix("refs/heads/").unwrap_or(head_name)
-        .to_string();
-    Ok(branch_name)
+    if gitmodules_rewritten {
+        let gitmodules_path = path::PathBuf::from(repo.workdir().expect("not bare").join(".gitmodules"));
+        let _ = match git2_add_and_commit(
+            repo, 
+            signature, 
+            &[&gitmodules_path], 
+            "Point submodules to forks [gh.rs]",
+        ).await {
+            Err(e) => return_cmd_err!("Failed to commit .gitmodules, error: {e}"),
+            Ok(r) => r,
+        };
+        let _ = match git2_push(
+            repo,
+            "origin",
+            remote_url,
+            &false,
+
+            user.login.clone().as_str(), 
+            token, 
+            ssh_private_key,
+        ) {
+            Err(e) => return_cmd_err!("Failed to push, error: {e}"),
+            Ok(r) => r,
+        };
+    }
+    Ok(())
 }
 
-fn git2_push(
-    repo: &git2::Repository,
+// Replaces bare mirror at path with working clone of pushed remote
+async fn mirror_replace_with_clone(
+    mirror_repo: git2::Repository,
+    path: &path::PathBuf,
     remote_name: &str,
     remote_url: &str,
-    mirror: &bool,
 
-    username: &str,
-    password: &str,
+    user: &octocrab::models::Author,
+    token: &str,
     ssh_private_key: &ssh_key::PrivateKey,
-) -> GhRsResult<()> {
-    let mut remote = git2_remote_recreate(repo, remote_name, remote_url)?;
-    let mut opts = git2::PushOptions::new();
-    let mut callbacks = git2::RemoteCallbacks::new();
-    callbacks.credentials(move |url, username_from_url, allowed| 
-        git2_credentials(
-            url, username_from_url, allowed,
-            username, password, ssh_private_key
-        )
-     );
-    opts.remote_callbacks(callbacks);
-    let mut refspecs: Vec<String> = vec![];
-    refspecs.push(format!("refs/heads/{}", git2_default_branch_name(&repo)?));
-    if *mirror {
-        repo.remote_add_push(remote_name, format!("refs/remotes/{remote_name}/*:refs/heads/*").as_str())?;
-        repo.remote_add_push(remote_name, "refs/tags/*:refs/tags/*")?;
-        let branches = repo.branches(Some(git2::BranchType::Remote))?;
-        for branch in branches.flatten() {
-            let name = branch.0.name();
-            if let Ok(Some(name)) = name {
-                let branch = &name[name.find('/').unwrap()..];
-                refspecs.push(format!("refs/remotes/{name}:refs/heads{branch}"));
-            }
-        }
-        let tags = repo.tag_names(None)?;
-        for tag in tags.iter().flatten() {
-            refspecs.push(format!("refs/tags/{tag}:refs/tags/{tag}"));
-        }
+) -> GhRsResult<git2::Repository> {
+    let last_commit_id = mirror_repo.head().expect("has head")
+        .peel_to_commit().expect("has commit")
+        .id();
+    // keep downloaded LFS objects for the working clone
+    let lfs_storage_path = path::PathBuf::from(lfs::storage_dir(&mirror_repo));
+    let lfs_storage_tmp_path = path!("{path_display}.lfs", path_display = path_display(path));
+    if lfs_storage_path.is_dir().await {
+        let _ = match fs::rename(&lfs_storage_path, &lfs_storage_tmp_path).await {
+            Err(e) => return_cmd_err!("Failed to move LFS objects, error: {e}"),
+            Ok(r) => r,
+        };
     }
-    remote.push(&refspecs, Some(&mut opts))?;
-    Ok(())
+    let _ = match fs::remove_dir_all(path).await {
+        Err(e) => return_cmd_err!(
+            "Failed to remove directory \"{path_display}\", error: {e}", 
+            path_display = path_display(path)
+        ),
+        Ok(r) => r,
+    };
+
+    let repo = match git2_clone(
+        remote_url,
+        path,
+        &false,
+
+        user.login.clone().as_str(), 
+        token, 
+        ssh_private_key,
+    ) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to clone repo, error: {e}"),
+    };
+    
+    let _ = match git2_fetch_until_commit(
+        &repo,
+        remote_name,
+        remote_url,
+        &last_commit_id,
+        &tokio::time::Duration::new(0, 500_000_000),
+        10,
+
+        user.login.clone().as_str(), 
+        token, 
+        ssh_private_key,
+    ).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to fetch remote until latest commit, error: {e}"),
+    };
+
+    if lfs_storage_tmp_path.is_dir().await {
+        let _ = match fs::rename(&lfs_storage_tmp_path, lfs::storage_dir(&repo)).await {
+            Err(e) => return_cmd_err!("Failed to move LFS objects, error: {e}"),
+            Ok(r) => r,
+        };
+    }
+    if lfs::uses_lfs(&repo) {
+        match git2_lfs_checkout(&repo, remote_url, user.login.clone().as_str(), token).await {
+            Ok(count) => aprintln!("Checked out {count} LFS files"),
+            Err(e) => return_cmd_err!("Failed to check out LFS files, error: {e}"),
+        };
+    }
+    Ok(repo)
 }
 
+async fn run_fork_cmd(
+    external_path_raw_opt: &Option<&str>,
+    repo_name_raw_opt: &Option<&str>,
+    public_raw: &bool,
+    with_labels: &bool,
+    with_milestones: &bool,
+    with_releases: &bool,
+    with_wiki: &bool,
+    recurse_submodules: &bool,
+    strip_paths: &Vec<&str>,
+    strip_blobs_bigger_than_raw_opt: &Option<&str>,
+    mailmap_path_raw_opt: &Option<&str>,
+    org_raw_opt: &Option<&str>,
+    teams_raw: &Vec<&str>,
+    invites_raw: &Vec<&str>,
+    create_options_raw: &RepoCreateOptionsRaw,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
+    let username = user.login.clone();
+    let teams = get_teams(teams_raw, org_raw_opt)?;
+    let invites = get_invites(invites_raw)?;
+    let owner = get_repo_owner(&octocrab_client, &username, org_raw_opt).await?;
+    let create_options = get_repo_create_options(create_options_raw, public_raw, org_raw_opt).await?;
+    let rewrite_options = &get_rewrite_options(strip_paths, strip_blobs_bigger_than_raw_opt, mailmap_path_raw_opt)?;
+    let (external_username, external_repo_name, external_gh_repo) = get_external_path(&octocrab_client, external_path_raw_opt, cli_only).await?;
 
-fn git2_clone(
-    clone_url: &str,
-    clone_to_path: &path::PathBuf,
-    clone_bare: &bool,
+    let repo_name = get_repo_name(&octocrab_client, &owner, repo_name_raw_opt, Some(&external_repo_name), &true, &false, cli_only).await?;
+    let repo_public = get_is_public_with_options(public_raw, &create_options, cli_only).await;
 
-    username: &str,
-    password: &str,
-    ssh_private_key: &ssh_key::PrivateKey,
-) -> GhRsResult<git2::Repository> {
-    let mut fetch_options = git2::FetchOptions::new();
-    let mut callbacks = git2::RemoteCallbacks::new();
-    callbacks.credentials(move |url, username_from_url, allowed| 
-        git2_credentials(
-            url, username_from_url, allowed,
-            username, password, ssh_private_key
-        )
-    );
-    fetch_options.remote_callbacks(callbacks);
-    let checkout_builder = git2::build::CheckoutBuilder::new();
-    let repo = git2::build::RepoBuilder::new()
-        .bare(*clone_bare)
-        .remote_create(git2_remote_recreate)
-        .fetch_options(fetch_options)
-        .with_checkout(checkout_builder)
-        .clone(clone_url, path_to_sync(clone_to_path).as_path())?;
 
-    Ok(repo)
-}
+    let path = path!("./{repo_name}");
+    let _ = match fs::create_dir(&path).await {
+        Err(e) => return_cmd_err!("Failed to create directory ./{repo_name}, error: {e}"),
+        Ok(r) => r,
+    };
+    
+    let ssh_private_key = match get_or_create_ssh_key(&octocrab_client).await {
+        Ok(k) => k,
+        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
+    };
+
+    let external_url = format!("https://github.com/{external_username}/{external_repo_name}.git");
+    let external_repo = fork_mirror(
+        &octocrab_client,
+        &token,
+        &user,
+        &ssh_private_key,
+        &external_username,
+        &external_repo_name,
+        &external_gh_repo,
+        &owner,
+        &repo_name,
+        &repo_public,
+        &path,
+        &ForkCopyOptions {
+            labels: *with_labels,
+            milestones: *with_milestones,
+            releases: *with_releases,
+            wiki: *with_wiki,
+        },
+        &create_options,
+        rewrite_options,
+        cli_only,
+    ).await?;
+    apply_teams(&octocrab_client, &owner, &repo_name, &teams).await?;
+    apply_invites(&octocrab_client, &owner, &repo_name, &invites).await?;
+
+    let remote_name = "origin";
+    let remote_url = format!("https://github.com/{owner}/{repo_name}.git");
+    let mut repo = mirror_replace_with_clone(
+        external_repo,
+        &path,
+        remote_name,
+        &remote_url,
+
+        &user,
+        &token,
+        &ssh_private_key,
+    ).await?;
+
+    if *recurse_submodules {
+        let sig = get_signature(&user).await?;
+        let mut forked_urls = vec![];
+        fork_submodules(
+            &octocrab_client,
+            &token,
+            &user,
+            &ssh_private_key,
+            &sig,
+            &mut repo,
+            &remote_url,
+            &external_url,
+            &owner,
+            cli_only,
+            &mut forked_urls,
+        ).await?;
+        if !forked_urls.is_empty() {
+            aprintln!("Forked submodules:");
+            for (original_url, forked_url) in &forked_urls {
+                aprintln!("  {original_url} -> {forked_url}");
+            }
+        }
+    }
+
+    let default_branch_name = match git2_default_branch_name(&repo) {
+        Ok(n) => n,
+        Err(e) => return_cmd_err!("Failed to get default branch name, error: {e}"),
+    };
 
-async fn git2_fetch_until_commit<'repo>(
-    repo: &'repo git2::Repository,
-    remote_name: &str,
-    remote_url: &str,
-    commit_id: &git2::Oid,
-    retry_timeout_duration: &tokio::time::Duration,
-    retries_limit: usize,
+    let _ = match git2_set_branch_upstream(&repo, default_branch_name.as_str(), remote_name) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to set \"{default_branch_name}\" branch upstream, error: {e}"),
+    };
+    let remote_external = match repo.remote("external", &external_url) {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to create \"external\" remote, error: {e}"),
+    };
 
-    username: &str,
-    password: &str,
-    ssh_private_key: &ssh_key::PrivateKey,
-) -> GhRsResult<()> {
-    if repo.find_commit(commit_id.to_owned()).is_ok() { return Ok(()); }
+    // license can't be set through the API without auto init, it comes with the mirrored LICENSE file
+    if let Some(external_license) = &external_gh_repo.license {
+        let license_matches = octocrab_client.repos(owner.clone(), repo_name.clone()).get().await
+            .ok()
+            .and_then(|r| r.license)
+            .map(|l| l.key == external_license.key)
+            .unwrap_or(false);
+        if !license_matches {
+            aprintln!(
+                "License {spdx_id} of {external_repo_name} is not detected on {repo_name} yet, check that LICENSE file is on the default branch",
+                spdx_id = external_license.spdx_id,
+            );
+        }
+    }
 
-    let mut remote = git2_remote_recreate(repo, remote_name, remote_url)?;
+    aprintln!("{hr}\n\nSUCCESS! Forked {external_repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
 
-    let mut retries = 0;
-    while retries <= retries_limit {
-        let started_at = tokio::time::Instant::now();
-        remote.connect(git2::Direction::Fetch)?;
-        let mut fetch_options = git2::FetchOptions::new();
-        let mut callbacks = git2::RemoteCallbacks::new();
-        callbacks.credentials(move |url, username_from_url, allowed| 
-            git2_credentials(
-                url, username_from_url, allowed,
-                username, password, ssh_private_key
-            )
-        );
-        fetch_options.remote_callbacks(callbacks);
-        remote.download(&[] as &[&str], Some(&mut fetch_options))?;
-        remote.disconnect()?;
-        remote.update_tips(None, true, git2::AutotagOption::Unspecified, None)?;
+// [protect] section of gh.rs config over protect::ProtectionPolicy::default():
+//   [protect]
+//   required-reviews = 2
+//   status-checks = build,test
+async fn get_protection_policy() -> GhRsResult<protect::ProtectionPolicy> {
+    let mut policy = protect::ProtectionPolicy::default();
+    let config = match get_gh_rs_config().await {
+        Some(c) => c,
+        None => return Ok(policy),
+    };
+    let section_name = "protect";
+    let get_bool = |key: &str, value_default: bool| -> GhRsResult<bool> {
+        match config.get(section_name, key) {
+            Some(value) => match parse_bool(&value) {
+                Some(b) => Ok(b),
+                None => return_cmd_err!("Invalid \"{key}\" value \"{value}\" in [{section_name}], expected true or false"),
+            },
+            None => Ok(value_default),
+        }
+    };
+    if let Some(value) = config.get(section_name, "required-reviews") {
+        policy.required_reviews = match value.trim().parse::<u32>() {
+            Ok(count) if count <= 6 => count,
+            _ => return_cmd_err!("Invalid \"required-reviews\" value \"{value}\" in [{section_name}], expected 0 to 6"),
+        };
+    }
+    if let Some(value) = config.get(section_name, "status-checks") {
+        policy.status_checks = value.split(',')
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .collect();
+    }
+    policy.dismiss_stale_reviews = get_bool("dismiss-stale-reviews", policy.dismiss_stale_reviews)?;
+    policy.code_owner_reviews = get_bool("code-owner-reviews", policy.code_owner_reviews)?;
+    policy.strict_status_checks = get_bool("strict-status-checks", policy.strict_status_checks)?;
+    policy.linear_history = get_bool("linear-history", policy.linear_history)?;
+    policy.allow_force_pushes = get_bool("allow-force-pushes", policy.allow_force_pushes)?;
+    policy.allow_deletions = get_bool("allow-deletions", policy.allow_deletions)?;
+    policy.enforce_admins = get_bool("enforce-admins", policy.enforce_admins)?;
+    Ok(policy)
+}
 
-        if repo.find_commit(commit_id.to_owned()).is_ok() { return Ok(()); }
-        
-        tokio::time::sleep_until(started_at + retry_timeout_duration.to_owned()).await;
-        retries += 1;
+// Rulesets where available (public repos, paid plans), classic branch protection otherwise.
+// branch_name_opt None protects default branch
+async fn apply_protection(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    branch_name_opt: Option<&str>,
+    default_branch_name: &str,
+    policy: &protect::ProtectionPolicy,
+) -> GhRsResult<()> {
+    let branch_name = branch_name_opt.unwrap_or(default_branch_name);
+    match octocrab_rulesets_upsert(octocrab_client, owner, repo_name, &protect::ruleset_body(policy, branch_name_opt)).await {
+        Ok(ruleset) => aprintln!("Protected {branch_name} branch with \"{ruleset_name}\" ruleset", ruleset_name = ruleset.name),
+        Err(rulesets_error) => {
+            aprintln!("Rulesets are unavailable, falling back to classic branch protection, error: {rulesets_error}");
+            let _ = match octocrab_branch_protection_update(
+                octocrab_client,
+                owner,
+                repo_name,
+                branch_name,
+                &protect::branch_protection_body(policy),
+            ).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to protect {branch_name} branch, error: {e}"),
+            };
+            aprintln!("Protected {branch_name} branch");
+        },
     }
-    return_cmd_err!("Retries limit reached")
+    for policy_line in policy.describe() {
+        aprintln!("  {policy_line}");
+    }
+    Ok(())
 }
-    
 
-async fn get_auth(
+// Repo from owner/repo or url, GitHub "origin" remote of current directory otherwise
+async fn get_target_repo(
+    octocrab_client: &octocrab::Octocrab,
+    repo_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<(String, String, octocrab::models::Repository)> {
+    if repo_raw_opt.is_some() {
+        return get_external_path(octocrab_client, repo_raw_opt, cli_only).await;
+    }
+    let origin_url_opt = git2::Repository::open(".").ok()
+        .and_then(|r| r.find_remote("origin").ok().and_then(|remote| remote.url().map(|u| u.to_string())));
+    if let Some(origin_url) = origin_url_opt {
+        if let Some((owner, repo_name)) = resolve_github_git_url(&origin_url) {
+            let repo_path = format!("{owner}/{repo_name}");
+            return get_external_path(octocrab_client, &Some(repo_path.as_str()), cli_only).await;
+        }
+    }
+    get_external_path(octocrab_client, &None, cli_only).await
+}
+
+async fn run_protect_cmd(
+    repo_raw_opt: &Option<&str>,
+    branch_name_raw_opt: &Option<&str>,
     token_raw_opt: &Option<&str>,
     cli_only: &bool,
-) -> GhRsResult<(octocrab::Octocrab, String, octocrab::models::Author)> {
-    let cli_only_error = GhRsError::CliOnly("access token".to_string());
+) -> GhRsResult<()> {
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    let default_branch_name = gh_repo.default_branch.clone().unwrap_or("main".to_string());
+    let policy = get_protection_policy().await?;
+    apply_protection(&octocrab_client, &owner, &repo_name, *branch_name_raw_opt, &default_branch_name, &policy).await?;
 
-    async fn prompt_token() -> String {
-        loop {
-            let token_answer = prompt("GitHub access token", None).await.trim().to_string();
-            if token_answer.len() != 40 || !re_token().is_match(&token_answer) {
-                aprintln!("Invalid token format");
-                continue;
+    aprintln!("{hr}\n\nSUCCESS! Protected {owner}/{repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
+
+// Live state of repo diffed against its merged settings
+async fn get_settings_plan(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    repo_settings: &settings::RepoSettings,
+    base_policy: &protect::ProtectionPolicy,
+) -> GhRsResult<Vec<(settings::SettingsAction, Vec<String>)>> {
+    let live_repo: serde_json::Value = octocrab_client.get(format!("/repos/{owner}/{repo_name}"), None::<&()>).await?;
+    let live_topics = match &repo_settings.topics {
+        Some(_) => {
+            let octo_topics: OctocrabModelTopics = octocrab_client.get(format!("/repos/{owner}/{repo_name}/topics"), None::<&()>).await?;
+            octo_topics.names
+        },
+        None => vec![],
+    };
+    let live_labels: Vec<settings::LabelSettings> = match &repo_settings.labels {
+        Some(_) => octocrab_labels_list(octocrab_client, owner, repo_name).await?.into_iter()
+            .map(|l| settings::LabelSettings { name: l.name, color: l.color, description: l.description })
+            .collect(),
+        None => vec![],
+    };
+    let mut live_collaborators: Vec<(String, String)> = vec![];
+    if repo_settings.collaborators.is_some() {
+        for collaborator in octocrab_collaborators_list(octocrab_client, owner, repo_name, "direct").await? {
+            // owner can't be removed or downgraded
+            if collaborator.login.eq_ignore_ascii_case(owner) { continue; }
+            let permission = settings::permission_normalize(collaborator.role_name.as_deref().unwrap_or("read"));
+            live_collaborators.push((collaborator.login, permission));
+        }
+        // pending invitations count as collaborators, put updates them
+        for invitation in octocrab_repo_invitations_list(octocrab_client, owner, repo_name).await? {
+            if let Some(invitee) = invitation.invitee {
+                live_collaborators.push((invitee.login, settings::permission_normalize(&invitation.permissions)));
             }
-            return token_answer;
         }
     }
-    
-    let mut tried_env = false;
-    let mut tried_config = false;
-    let mut tried_credentials = false;
-    let mut tried_opt = false;
-    loop {
-        let token_raw = match token_raw_opt {
-            None => match get_github_token_from_machine(
-                &mut tried_env, 
-                &mut tried_config, 
-                &mut tried_credentials
-            ).await {
-                Some(token_found) => token_found,
-                None => match *cli_only {
-                    true => return Err(cli_only_error),
-                    false => prompt_token().await,
+    let mut actions = settings::plan_repo(repo_settings, &live_repo, &live_topics, &live_labels, &live_collaborators);
+
+    if let Some(branch_rules) = &repo_settings.branch_rules {
+        let policy = branch_rules.policy(base_policy);
+        let branch_name_opt = branch_rules.branch.as_deref();
+        let branch_display = branch_name_opt.unwrap_or("default");
+        let ruleset_name = protect::ruleset_name(branch_name_opt);
+        let status_opt = match octocrab_rulesets_list(octocrab_client, owner, repo_name).await {
+            Ok(rulesets) => match rulesets.iter().find(|r| r.name == ruleset_name) {
+                Some(ruleset) => {
+                    let live_ruleset = octocrab_rulesets_get(octocrab_client, owner, repo_name, ruleset.id).await?;
+                    match protect::ruleset_matches(&live_ruleset, &protect::ruleset_body(&policy, branch_name_opt)) {
+                        true => None,
+                        false => Some(format!("update \"{ruleset_name}\" ruleset")),
+                    }
                 },
+                None => Some(format!("create \"{ruleset_name}\" ruleset")),
             },
-            Some(token_raw_possible) => {
-                if !tried_opt && token_raw_possible.len() == 40 && re_token().is_match(token_raw_possible) {
-                    tried_opt = true;
-                    token_raw_possible.to_string()
-                } else {
-                    match get_github_token_from_machine(
-                        &mut tried_env, 
-                        &mut tried_config, 
-                        &mut tried_credentials
-                    ).await {
-                        Some(token_found) => token_found,
-                        None => match *cli_only {
-                            true => return Err(cli_only_error),
-                            false => prompt_token().await,
-                        },
-                    }
-                }
-            }
+            // classic branch protection can't be diffed as reliably, always applied
+            Err(_) => Some("apply classic branch protection (rulesets are unavailable)".to_string()),
         };
-
-        let octocrab_client = octocrab::OctocrabBuilder::default()
-            .personal_token(token_raw.clone())
-            .build()?;
-        match octocrab_client.current().user().await {
-            Ok(user) => {
-                return Ok((octocrab_client, token_raw, user));
-            }
-            Err(e) => {
-                aprintln!("Invalid answer received from API, possible access token is wrong, error: {e}");
-                if *cli_only {
-                    return Err(cli_only_error);
-                }
-                continue;
-            }
+        if let Some(status) = status_opt {
+            let mut lines = vec![format!("branch rules of {branch_display} branch: {status}")];
+            lines.extend(policy.describe().into_iter().map(|l| format!("  {l}")));
+            actions.push((settings::SettingsAction::Protect(branch_rules.branch.clone(), policy), lines));
         }
     }
+    Ok(actions)
 }
 
-async fn get_repo_name(
+async fn apply_settings_action(
     octocrab_client: &octocrab::Octocrab,
-    username: &str,
-    repo_name_raw_opt: &Option<&str>,
-    repo_name_prompt_default: Option<&str>,
+    owner: &str,
+    repo_name: &str,
+    default_branch_name: &str,
+    action: &settings::SettingsAction,
+) -> GhRsResult<()> {
+    match action {
+        settings::SettingsAction::UpdateRepo(fields) => {
+            let _ = octocrab_repos_update(octocrab_client, owner, repo_name, &serde_json::Value::Object(fields.clone())).await?;
+        },
+        settings::SettingsAction::ReplaceTopics(topics) => {
+            let _ = octocrab_repos_replace_topics(octocrab_client, owner, repo_name, topics).await?;
+        },
+        settings::SettingsAction::CreateLabel(label) => {
+            let _ = octocrab_labels_create(octocrab_client, owner, repo_name, &serde_json::json!({
+                "name": label.name,
+                "color": label.color,
+                "description": label.description.clone().unwrap_or_default(),
+            })).await?;
+        },
+        settings::SettingsAction::UpdateLabel(live_name, label) => {
+            let mut fields = serde_json::json!({
+                "new_name": label.name,
+                "color": label.color,
+            });
+            if let Some(description) = &label.description {
+                fields["description"] = serde_json::json!(description);
+            }
+            let _ = octocrab_labels_update(octocrab_client, owner, repo_name, live_name, &fields).await?;
+        },
+        settings::SettingsAction::DeleteLabel(name) => {
+            octocrab_labels_delete(octocrab_client, owner, repo_name, name).await?;
+        },
+        settings::SettingsAction::PutCollaborator(login, permission) => {
+            let _ = octocrab_collaborators_put(octocrab_client, owner, repo_name, login, permission).await?;
+        },
+        settings::SettingsAction::RemoveCollaborator(login) => {
+            octocrab_collaborators_remove(octocrab_client, owner, repo_name, login).await?;
+        },
+        settings::SettingsAction::Protect(branch_name_opt, policy) => {
+            apply_protection(octocrab_client, owner, repo_name, branch_name_opt.as_deref(), default_branch_name, policy).await?;
+        },
+    }
+    Ok(())
+}
+
+async fn run_settings_cmd(
+    file_path_raw: &str,
+    apply: &bool,
+    token_raw_opt: &Option<&str>,
     cli_only: &bool,
-) -> GhRsResult<String> {
-    let cli_only_error = GhRsError::CliOnly("new repo name".to_string());
+) -> GhRsResult<()> {
+    let settings_file = settings::load(path!("{file_path_raw}").as_ref())?;
+    let base_policy = get_protection_policy().await?;
+    let (octocrab_client, _token, user) = get_auth(token_raw_opt, cli_only).await?;
 
-    async fn prompt_repo_name(prompt_default: Option<&str>) -> String {
-        loop {
-            let repo_name_answer = prompt("GitHub new repo name", prompt_default).await.trim().to_string();
-            if repo_name_answer.is_empty() {
-                aprintln!("Invalid name format, empty string");
-                continue;
-            } else if !re_repo_name().is_match(&repo_name_answer) {
-                aprintln!("Invalid name format");
-                continue;
-            } else if path!("./{repo_name_answer}").exists().await {
-                aprintln!("Directory with name {repo_name_answer} already exists");
-                continue;
+    let mut owners: Vec<&str> = vec![];
+    for repo_settings in &settings_file.repos {
+        for pattern in &repo_settings.patterns {
+            let (owner, _) = settings::pattern_split(pattern, settings_file.owner.as_deref());
+            if !owners.iter().any(|o| o.eq_ignore_ascii_case(owner)) {
+                owners.push(owner);
+            }
+        }
+    }
+    let mut matched_repos: Vec<(String, String, String, settings::RepoSettings)> = vec![];
+    for owner in owners {
+        let owner_repos = match octocrab_owner_repos(&octocrab_client, owner, &user.login).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to list repos of {owner}, error: {e}"),
+        };
+        for gh_repo in owner_repos {
+            let repo_owner = gh_repo.owner.as_ref().map(|o| o.login.clone()).unwrap_or(owner.to_string());
+            if let Some(repo_settings) = settings::settings_for(&settings_file, &repo_owner, &gh_repo.name) {
+                // archived repos are read-only
+                if gh_repo.archived == Some(true) {
+                    aprintln!("Skipping archived {repo_owner}/{repo_name}", repo_name = gh_repo.name);
+                    continue;
+                }
+                let default_branch_name = gh_repo.default_branch.clone().unwrap_or("main".to_string());
+                matched_repos.push((repo_owner, gh_repo.name.clone(), default_branch_name, repo_settings));
             }
-            return repo_name_answer;
         }
     }
+    if matched_repos.is_empty() {
+        return_cmd_err!("No repos match the settings file patterns");
+    }
 
-    loop {
-        let repo_name_raw = match repo_name_raw_opt {
-            Some(repo_name_raw_possible) => match re_repo_name().is_match(repo_name_raw_possible) {
-                true => repo_name_raw_possible.to_string(),
-                false => {
-                    aprintln!("Invalid name format");
-                    match *cli_only {
-                        true => return Err(cli_only_error),
-                        false => prompt_repo_name(repo_name_prompt_default).await,
+    let mut plans = vec![];
+    let mut failed_repos: Vec<String> = vec![];
+    for (owner, repo_name, default_branch_name, repo_settings) in &matched_repos {
+        match get_settings_plan(&octocrab_client, owner, repo_name, repo_settings, &base_policy).await {
+            Ok(actions) if actions.is_empty() => aprintln!("{owner}/{repo_name}: no changes"),
+            Ok(actions) => {
+                aprintln!("{owner}/{repo_name}:");
+                for (_, lines) in &actions {
+                    for line in lines {
+                        aprintln!("  {line}");
                     }
-                },
+                }
+                plans.push((owner, repo_name, default_branch_name, actions));
+            },
+            Err(e) => {
+                aprintln!("{owner}/{repo_name}: failed to read live state, error: {e}");
+                failed_repos.push(format!("{owner}/{repo_name}"));
+            },
+        }
+    }
+    let changes_count: usize = plans.iter().map(|(_, _, _, actions)| actions.len()).sum();
+    aprintln!(
+        "{hr}\n\n{changes_count} changes in {changed_count} of {repos_count} repos",
+        hr = get_hr(),
+        changed_count = plans.len(),
+        repos_count = matched_repos.len(),
+    );
+    if !*apply || changes_count == 0 {
+        if !failed_repos.is_empty() {
+            return_cmd_err!("Failed to plan {failed}", failed = failed_repos.join(", "));
+        }
+        return Ok(());
+    }
+    if !*cli_only && !prompt_bool(&format!("Apply {changes_count} changes?"), Some(false)).await {
+        return_cmd_err!("Settings are not applied");
+    }
+
+    for (owner, repo_name, default_branch_name, actions) in &plans {
+        let mut errors = vec![];
+        for (action, lines) in actions {
+            if let Err(e) = apply_settings_action(&octocrab_client, owner, repo_name, default_branch_name, action).await {
+                errors.push(format!("{line}: {e}", line = lines.first().map(|l| l.as_str()).unwrap_or("")));
             }
-            None => match *cli_only {
-                true => return Err(cli_only_error),
-                false => prompt_repo_name(repo_name_prompt_default).await,
+        }
+        match errors.is_empty() {
+            true => aprintln!("Applied {count} changes to {owner}/{repo_name}", count = actions.len()),
+            false => {
+                aprintln!("Failed {failed} of {count} changes to {owner}/{repo_name}:", failed = errors.len(), count = actions.len());
+                for error in errors {
+                    aprintln!("  {error}");
+                }
+                failed_repos.push(format!("{owner}/{repo_name}"));
             },
+        }
+    }
+    if !failed_repos.is_empty() {
+        return_cmd_err!("Settings are not fully applied to {failed}", failed = failed_repos.join(", "));
+    }
+
+    aprintln!("{hr}\n\nSUCCESS! Applied settings to {count} repos.\nHappy hacking & have a nice day :)", hr = get_hr(), count = plans.len());
+    Ok(())
+}
+
+// Flags only, profiles are for new repos
+fn get_repo_edit_options(options_raw: &RepoCreateOptionsRaw) -> GhRsResult<OctocrabReposCreateOptions> {
+    let (allow_merge_commit, allow_squash_merge, allow_rebase_merge) = match &options_raw.merge_methods {
+        Some(m) => {
+            let (merge, squash, rebase) = parse_merge_methods(m)?;
+            (Some(merge), Some(squash), Some(rebase))
+        },
+        None => (None, None, None),
+    };
+    Ok(OctocrabReposCreateOptions {
+        homepage: options_raw.homepage.clone(),
+        has_issues: options_raw.has_issues,
+        has_projects: options_raw.has_projects,
+        has_wiki: options_raw.has_wiki,
+        has_discussions: options_raw.has_discussions,
+        is_template: options_raw.is_template,
+        allow_merge_commit,
+        allow_squash_merge,
+        allow_rebase_merge,
+        delete_branch_on_merge: options_raw.delete_branch_on_merge,
+        allow_auto_merge: options_raw.allow_auto_merge,
+        visibility: None,
+        secret_scanning: options_raw.secret_scanning,
+        secret_scanning_push_protection: options_raw.push_protection,
+    })
+}
+
+async fn run_repo_edit_cmd(
+    repo_raw_opt: &Option<&str>,
+    repo_description_raw_opt: &Option<&str>,
+    repo_topics_raw_opt: &Option<&str>,
+    default_branch_raw_opt: &Option<&str>,
+    options_raw: &RepoCreateOptionsRaw,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let options = get_repo_edit_options(options_raw)?;
+    let mut fields = match serde_json::to_value(&options) {
+        Ok(serde_json::Value::Object(m)) => m,
+        _ => serde_json::Map::new(),
+    };
+    if let Some(repo_description) = repo_description_raw_opt {
+        fields.insert("description".to_string(), serde_json::json!(repo_description));
+    }
+    if let Some(default_branch) = default_branch_raw_opt {
+        fields.insert("default_branch".to_string(), serde_json::json!(default_branch));
+    }
+    let has_security = options.secret_scanning.is_some() || options.secret_scanning_push_protection.is_some();
+    if fields.is_empty() && !has_security && repo_topics_raw_opt.is_none() {
+        return_cmd_err!("Nothing to edit, set at least one option, see --help");
+    }
+
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, _gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    let mut changed = fields.keys().cloned().collect::<Vec<String>>();
+    if !fields.is_empty() {
+        let _ = match octocrab_repos_update(&octocrab_client, &owner, &repo_name, &serde_json::Value::Object(fields)).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to edit {owner}/{repo_name}, error: {e}"),
+        };
+    }
+    if has_security {
+        let _ = match octocrab_repos_update_security(&octocrab_client, &owner, &repo_name, &options).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to update security settings of {owner}/{repo_name}, error: {e}"),
+        };
+        changed.push("security_and_analysis".to_string());
+    }
+    if repo_topics_raw_opt.is_some() {
+        let topics = get_repo_topics(repo_topics_raw_opt, &[], cli_only).await?;
+        let _ = match octocrab_repos_replace_topics(&octocrab_client, &owner, &repo_name, &topics).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to set topics of {owner}/{repo_name}, error: {e}"),
+        };
+        changed.push("topics".to_string());
+    }
+
+    aprintln!(
+        "{hr}\n\nSUCCESS! Edited {owner}/{repo_name} repo: {changed}.\nHappy hacking & have a nice day :)",
+        hr = get_hr(),
+        changed = changed.join(", "),
+    );
+    Ok(())
+}
+
+async fn run_repo_rename_cmd(
+    repo_raw_opt: &Option<&str>,
+    repo_name_raw_opt: &Option<&str>,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, _gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    let new_repo_name = get_repo_name(&octocrab_client, &owner, repo_name_raw_opt, Some(&repo_name), &false, &false, cli_only).await?;
+    let _ = match octocrab_repos_update(&octocrab_client, &owner, &repo_name, &serde_json::json!({ "name": new_repo_name })).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to rename {owner}/{repo_name}, error: {e}"),
+    };
+    aprintln!("Renamed {owner}/{repo_name} to {owner}/{new_repo_name}");
+
+    // GitHub redirects old urls, but only until a repo with the old name is created
+    if let Ok(repo) = git2::Repository::open(".") {
+        let remote_names = match git2_remotes_retarget(&repo, &owner, &repo_name, &owner, &new_repo_name) {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to update local remotes, error: {e}"),
         };
-        match octocrab_client.repos(username, repo_name_raw.clone()).get().await {
-            Err(e) => {
-                return Ok(repo_name_raw);
-            }
-            Ok(repo) => {
-                aprintln!("Repo with name {repo_name_raw} already exists");
-                if *cli_only {
-                    return Err(cli_only_error);
-                }
-                continue;
-            }
+        for remote_name in remote_names {
+            aprintln!("Updated url of local \"{remote_name}\" remote");
         }
     }
+
+    aprintln!("{hr}\n\nSUCCESS! Renamed {repo_name} repo to {new_repo_name}.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
 }
 
-async fn get_repo_description(
-    repo_description_raw_opt: &Option<&str>,
+async fn run_repo_archive_cmd(
+    repo_raw_opt: &Option<&str>,
+    archived: &bool,
+    token_raw_opt: &Option<&str>,
     cli_only: &bool,
-) -> GhRsResult<String> {
-    let cli_only_error = GhRsError::CliOnly("new repo description".to_string());
+) -> GhRsResult<()> {
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    let action = match *archived {
+        true => "Archived",
+        false => "Unarchived",
+    };
+    if gh_repo.archived.unwrap_or(false) == *archived {
+        aprintln!("{owner}/{repo_name} is already {state}", state = action.to_lowercase());
+        return Ok(());
+    }
+    let _ = match octocrab_repos_update(&octocrab_client, &owner, &repo_name, &serde_json::json!({ "archived": *archived })).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to update {owner}/{repo_name}, error: {e}"),
+    };
 
-    async fn prompt_repo_description() -> String {
-        loop {
-            let mut repo_description_answer = prompt("GitHub new repo description", None).await;
-            repo_description_answer = repo_description_answer.trim().to_string();
-            if repo_description_answer.len() <= 255 {
-                return repo_description_answer;
-            }
-            aprintln!("Too long description, maximum lenght 255, now: {len}", len = repo_description_answer.len());
-            continue;
-        }
+    aprintln!("{hr}\n\nSUCCESS! {action} {owner}/{repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
+
+async fn run_repo_visibility_cmd(
+    visibility_raw: &str,
+    repo_raw_opt: &Option<&str>,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let visibility = visibility_raw.trim().to_lowercase();
+    if !["public", "private", "internal"].contains(&visibility.as_str()) {
+        return_cmd_err!("Invalid visibility \"{visibility_raw}\", expected public, private or internal");
     }
-    
-    let repo_description = match repo_description_raw_opt {
-        Some(repo_description_raw) => {
-            if repo_description_raw.len() <= 255 {
-                repo_description_raw.to_string()
-            } else {
-                aprintln!("Too long description, maximum lenght 255, now: {len}", len = repo_description_raw.len());
-                match *cli_only {
-                    true => return Err(cli_only_error),
-                    false => prompt_repo_description().await,
-                }
-            }
-        }
-        None => match *cli_only {
-            true => return Err(cli_only_error),
-            false => prompt_repo_description().await,
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    if gh_repo.visibility.as_deref() == Some(visibility.as_str()) {
+        aprintln!("{owner}/{repo_name} is already {visibility}");
+        return Ok(());
+    }
+    let _ = match octocrab_repos_update(&octocrab_client, &owner, &repo_name, &serde_json::json!({ "visibility": visibility })).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to change visibility of {owner}/{repo_name}, error: {e}"),
+    };
+
+    aprintln!("{hr}\n\nSUCCESS! Made {owner}/{repo_name} repo {visibility}.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
+
+async fn run_repo_delete_cmd(
+    repo_raw_opt: &Option<&str>,
+    yes: &bool,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, _gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    let full_name = format!("{owner}/{repo_name}");
+    // --yes is for scripts only, interactively the name is always typed
+    if !*cli_only {
+        aprintln!("Deleting {full_name} removes its code, issues, pull requests, releases & wiki permanently");
+        let answer = prompt(&format!("Type {full_name} to confirm"), None).await.trim().to_string();
+        if answer != full_name {
+            return_cmd_err!("Confirmation does not match {full_name}, repo is not deleted");
         }
+    } else if !*yes {
+        return Err(GhRsError::CliOnly("--yes deletion confirmation".to_string()));
+    }
+    let _ = match octocrab_repos_delete(&octocrab_client, &owner, &repo_name).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to delete {full_name}, access token needs \"delete_repo\" scope, error: {e}"),
     };
-    Ok(repo_description)
+
+    aprintln!("{hr}\n\nSUCCESS! Deleted {full_name} repo, local clones are kept.\nHave a nice day :)", hr = get_hr());
+    Ok(())
 }
 
-fn resolve_github_path<'path>(github_path_or_url: &'path str) -> Option<(&'path str, &'path str)> {
-    let mut path = github_path_or_url;
-    path = path.strip_prefix("https://").unwrap_or(path);
-    path = path.strip_prefix("http://").unwrap_or(path);
-    path = path.strip_prefix("github.com").unwrap_or(path);
-    path = path.strip_prefix("/").unwrap_or(path);
-    path = path.strip_suffix("/").unwrap_or(path);
-    if path.contains("?") {
-        path = path.split_once("?").expect("found one").0;
-        path = path.strip_suffix("/").unwrap_or(path);
+const bulk_actions: [&str; 6] = ["archive", "unarchive", "make-private", "make-public", "delete", "add-topic"];
+
+#[derive(Debug, Clone, Default)]
+struct BulkRepoFilters {
+    name_regex: Option<regex::Regex>,
+    pushed_before: Option<chrono::DateTime<chrono::Utc>>,
+    fork: Option<bool>,
+    language: Option<String>,
+    topics: Vec<String>,
+}
+
+fn bulk_repo_matches(gh_repo: &octocrab::models::Repository, filters: &BulkRepoFilters) -> bool {
+    if let Some(name_regex) = &filters.name_regex {
+        if !name_regex.is_match(&gh_repo.name) { return false; }
     }
-    if path.contains("#") {
-        path = path.split_once("#").expect("found one").0;
-        path = path.strip_suffix("/").unwrap_or(path);
+    if let Some(pushed_before) = filters.pushed_before {
+        // never pushed counts as stale
+        if gh_repo.pushed_at.map(|p| p >= pushed_before).unwrap_or(false) { return false; }
     }
-    let mut path_splitter = path.splitn(3, "/");
-    let username = match path_splitter.next() {
-        Some(s) => s,
-        None => return None,
-    };
-    let mut repo_name = match path_splitter.next() {
-        Some(s) => s,
-        None => return None,
+    if let Some(fork) = filters.fork {
+        if gh_repo.fork.unwrap_or(false) != fork { return false; }
+    }
+    if let Some(language) = &filters.language {
+        let repo_language = gh_repo.language.as_ref().and_then(|l| l.as_str()).unwrap_or("");
+        if !repo_language.eq_ignore_ascii_case(language) { return false; }
+    }
+    let repo_topics = gh_repo.topics.clone().unwrap_or_default();
+    filters.topics.iter().all(|t| repo_topics.iter().any(|rt| rt.eq_ignore_ascii_case(t)))
+}
+
+// Repos already in the target state are skipped, archived repos are read-only
+fn bulk_action_applies(gh_repo: &octocrab::models::Repository, action: &str, new_topic_opt: Option<&str>) -> bool {
+    let archived = gh_repo.archived.unwrap_or(false);
+    let private = gh_repo.private.unwrap_or(false);
+    match action {
+        "archive" => !archived,
+        "unarchive" => archived,
+        "make-private" => !archived && !private,
+        "make-public" => !archived && private,
+        "delete" => true,
+        "add-topic" => !archived && !gh_repo.topics.clone().unwrap_or_default().iter()
+            .any(|t| Some(t.as_str()) == new_topic_opt),
+        _ => false,
+    }
+}
+
+fn octocrab_is_rate_limited(error: &GhRsError) -> bool {
+    match error {
+        GhRsError::OctocrabError(octocrab::Error::GitHub { source, .. }) => source.message.to_lowercase().contains("rate limit"),
+        _ => false,
+    }
+}
+
+// Until primary rate limit reset, a minute for secondary (abuse) rate limits
+async fn octocrab_rate_limit_wait(octocrab_client: &octocrab::Octocrab) -> tokio::time::Duration {
+    match octocrab_client.ratelimit().get().await {
+        Ok(rate_limit) if rate_limit.resources.core.remaining == 0 => {
+            let now = chrono::Utc::now().timestamp().max(0) as u64;
+            tokio::time::Duration::from_secs((rate_limit.resources.core.reset as u64).saturating_sub(now) + 1)
+        },
+        _ => tokio::time::Duration::from_secs(60),
+    }
+}
+
+async fn bulk_apply_action(
+    octocrab_client: &octocrab::Octocrab,
+    owner: &str,
+    repo_name: &str,
+    action: &str,
+    new_topic_opt: Option<&str>,
+    topics: &[String],
+) -> GhRsResult<()> {
+    let fields = match action {
+        "archive" => serde_json::json!({ "archived": true }),
+        "unarchive" => serde_json::json!({ "archived": false }),
+        "make-private" => serde_json::json!({ "private": true }),
+        "make-public" => serde_json::json!({ "private": false }),
+        "delete" => return octocrab_repos_delete(octocrab_client, owner, repo_name).await,
+        "add-topic" => {
+            let mut topics = topics.to_vec();
+            topics.push(new_topic_opt.expect("checked before").to_string());
+            let _ = octocrab_repos_replace_topics(octocrab_client, owner, repo_name, &topics).await?;
+            return Ok(());
+        },
+        _ => return_cmd_err!("Unknown action \"{action}\""),
     };
-    repo_name = repo_name.strip_suffix(".git").unwrap_or(repo_name);
-    if !re_username().is_match(username) { return None; }
-    if !re_repo_name().is_match(repo_name) { return None; }
-    Some((username, repo_name))
+    let _ = octocrab_repos_update(octocrab_client, owner, repo_name, &fields).await?;
+    Ok(())
 }
 
-async fn get_external_path(
+// Runs in spawned tasks, so reports retries count instead of printing (aprintln is not Send)
+async fn bulk_apply_action_with_retries(
     octocrab_client: &octocrab::Octocrab,
-    external_path_raw_opt: &Option<&str>,
+    owner: &str,
+    repo_name: &str,
+    action: &str,
+    new_topic_opt: Option<&str>,
+    topics: &[String],
+) -> (GhRsResult<()>, usize) {
+    let retries_max = 3;
+    let mut retries = 0;
+    loop {
+        match bulk_apply_action(octocrab_client, owner, repo_name, action, new_topic_opt, topics).await {
+            Err(e) if octocrab_is_rate_limited(&e) && retries < retries_max => {
+                retries += 1;
+                tokio::time::sleep(octocrab_rate_limit_wait(octocrab_client).await).await;
+            },
+            result => return (result, retries),
+        }
+    }
+}
+
+async fn run_repo_bulk_cmd(
+    action: &str,
+    new_topic_raw_opt: &Option<&str>,
+    owner_raw_opt: &Option<&str>,
+    name_regex_raw_opt: &Option<&str>,
+    pushed_before_raw_opt: &Option<&str>,
+    fork_opt: &Option<bool>,
+    language_raw_opt: &Option<&str>,
+    topics_raw: &Vec<&str>,
+    concurrency: &usize,
+    dry_run: &bool,
+    yes: &bool,
+    token_raw_opt: &Option<&str>,
     cli_only: &bool,
-) -> GhRsResult<(String, String, octocrab::models::Repository)> {
-    let cli_only_error = GhRsError::CliOnly("external repo url".to_string());
+) -> GhRsResult<()> {
+    if !bulk_actions.contains(&action) {
+        return_cmd_err!("Invalid action \"{action}\", expected {bulk_actions_list}", bulk_actions_list = bulk_actions.join(", "));
+    }
+    let new_topic_opt = match (action, new_topic_raw_opt) {
+        ("add-topic", Some(new_topic_raw)) => match project::topic_slug(new_topic_raw) {
+            Some(t) if t == new_topic_raw.to_lowercase() => Some(t),
+            _ => return_cmd_err!("Invalid topic \"{new_topic_raw}\", use lowercase letters, numbers & hyphens, up to 50 chars"),
+        },
+        ("add-topic", None) => return_cmd_err!("Set --new-topic for add-topic action"),
+        _ => None,
+    };
+    let filters = BulkRepoFilters {
+        name_regex: match name_regex_raw_opt {
+            Some(name_regex_raw) => match regex::Regex::new(name_regex_raw) {
+                Ok(r) => Some(r),
+                Err(e) => return_cmd_err!("Invalid --name-regex, error: {e}"),
+            },
+            None => None,
+        },
+        pushed_before: match pushed_before_raw_opt {
+            Some(pushed_before_raw) => match chrono::NaiveDate::parse_from_str(pushed_before_raw, "%Y-%m-%d") {
+                Ok(d) => Some(chrono::DateTime::from_naive_utc_and_offset(d.and_hms_opt(0, 0, 0).expect("valid time"), chrono::Utc)),
+                Err(e) => return_cmd_err!("Invalid --pushed-before date \"{pushed_before_raw}\", expected YYYY-MM-DD, error: {e}"),
+            },
+            None => None,
+        },
+        fork: *fork_opt,
+        language: language_raw_opt.map(|l| l.to_string()),
+        topics: topics_raw.iter().map(|t| t.to_lowercase()).collect(),
+    };
 
-    async fn prompt_external_path() -> (String, String) {
-        loop {
-            let external_url_answer = prompt("GitHub external repo url", None).await.trim().to_string();
-            match resolve_github_path(&external_url_answer) {
-                Some((username_str, repo_name_str)) => return (username_str.to_string(), repo_name_str.to_string()),
-                None => {
-                    aprintln!(
-                        "Invalid url format, expected {ex_url} or just {ex_repo_path}",
-                        ex_url = get_gh_rs_github_url(),
-                        ex_repo_path = get_gh_rs_github_repo_path(),
-                    );
-                    continue;
-                }
-            }
+    let (octocrab_client, _token, user) = get_auth(token_raw_opt, cli_only).await?;
+    let owner = owner_raw_opt.unwrap_or(&user.login).to_string();
+    let owner_repos = match octocrab_owner_repos(&octocrab_client, &owner, &user.login).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to list repos of {owner}, error: {e}"),
+    };
+    let selected_repos: Vec<octocrab::models::Repository> = owner_repos.into_iter()
+        .filter(|r| bulk_repo_matches(r, &filters) && bulk_action_applies(r, action, new_topic_opt.as_deref()))
+        .collect();
+    if selected_repos.is_empty() {
+        aprintln!("No repos of {owner} match the filters for {action}");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = selected_repos.iter().map(|r| vec![
+        format!("{owner}/{name}", name = r.name),
+        match (r.private.unwrap_or(false), r.archived.unwrap_or(false)) {
+            (true, true) => "private, archived".to_string(),
+            (true, false) => "private".to_string(),
+            (false, true) => "public, archived".to_string(),
+            (false, false) => "public".to_string(),
+        },
+        match r.fork.unwrap_or(false) {
+            true => "fork".to_string(),
+            false => "".to_string(),
+        },
+        r.language.as_ref().and_then(|l| l.as_str()).unwrap_or("").to_string(),
+        r.pushed_at.map(|p| p.format("%Y-%m-%d").to_string()).unwrap_or("never".to_string()),
+    ]).collect();
+    for line in format_table(&["REPO", "VISIBILITY", "FORK", "LANGUAGE", "LAST PUSH"], &rows) {
+        aprintln!("{line}");
+    }
+    let repos_count = selected_repos.len();
+    let action_display = match &new_topic_opt {
+        Some(new_topic) => format!("{action} \"{new_topic}\""),
+        None => action.to_string(),
+    };
+    aprintln!("{hr}\n\n{action_display}: {repos_count} repos", hr = get_hr());
+    if *dry_run {
+        aprintln!("Dry run, nothing is changed");
+        return Ok(());
+    }
+    // --yes skips typed delete confirmation with --cli-only only
+    let is_confirmation_needed = !*yes || (action == "delete" && !*cli_only);
+    if is_confirmation_needed {
+        if *cli_only {
+            return Err(GhRsError::CliOnly("--yes confirmation".to_string()));
+        }
+        let is_confirmed = match action {
+            "delete" => {
+                let confirmation = format!("delete {repos_count}");
+                prompt(&format!("Deleting is permanent, type \"{confirmation}\" to confirm"), None).await.trim() == confirmation
+            },
+            _ => prompt_bool(&format!("Apply {action_display} to {repos_count} repos?"), Some(false)).await,
+        };
+        if !is_confirmed {
+            return_cmd_err!("Nothing is changed");
         }
     }
 
-    loop {
-        let (external_username, external_repo_name) = match external_path_raw_opt {
-            Some(external_path_raw_possible) => match resolve_github_path(external_path_raw_possible) {
-                None => {
-                    aprintln!(
-                        "Invalid url format, expected {ex_url} or just {ex_repo_path}",
-                        ex_url = get_gh_rs_github_url(),
-                        ex_repo_path = get_gh_rs_github_repo_path(),
-                    );
-                    match *cli_only {
-                        true => return Err(cli_only_error),
-                        false => prompt_external_path().await,
-                    }
-                },
-                Some((external_username_str, external_repo_name_str)) => (external_username_str.to_string(), external_repo_name_str.to_string()),
-            }
-            None => match *cli_only {
-                true => return Err(cli_only_error),
-                false => prompt_external_path().await,
-            }
+    let rate_limit_remaining_opt = octocrab_client.ratelimit().get().await.ok().map(|r| r.resources.core.remaining);
+    if let Some(rate_limit_remaining) = rate_limit_remaining_opt {
+        if rate_limit_remaining < repos_count {
+            aprintln!("Only {rate_limit_remaining} API requests remain, rate limited repos will wait for the limit reset");
+        }
+    }
+    aprintln!("Applying to {repos_count} repos, {concurrency} at once...");
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new((*concurrency).max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
+    for (i, gh_repo) in selected_repos.iter().enumerate() {
+        let octocrab_client = octocrab_client.clone();
+        let semaphore = semaphore.clone();
+        let owner = owner.clone();
+        let repo_name = gh_repo.name.clone();
+        let action = action.to_string();
+        let new_topic_opt = new_topic_opt.clone();
+        let topics = gh_repo.topics.clone().unwrap_or_default();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore is not closed");
+            let (result, retries) = bulk_apply_action_with_retries(&octocrab_client, &owner, &repo_name, &action, new_topic_opt.as_deref(), &topics).await;
+            (i, result.map_err(|e| e.to_string()), retries)
+        });
+    }
+    let mut results: Vec<Option<(Result<(), String>, usize)>> = vec![None; repos_count];
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((i, result, retries)) => results[i] = Some((result, retries)),
+            Err(e) => aprintln!("Bulk task failed, error: {e}"),
+        }
+    }
+
+    let mut failed_count = 0;
+    let name_width = selected_repos.iter().map(|r| r.name.chars().count()).max().unwrap_or(0);
+    for (gh_repo, result_opt) in selected_repos.iter().zip(results) {
+        let result_display = match result_opt {
+            Some((Ok(()), 0)) => "ok".to_string(),
+            Some((Ok(()), retries)) => format!("ok, after {retries} rate limit waits"),
+            Some((Err(e), _)) => {
+                failed_count += 1;
+                format!("failed, error: {e}")
+            },
+            None => {
+                failed_count += 1;
+                "failed, task panicked".to_string()
+            },
         };
-        match octocrab_client.repos(external_username.clone(), external_repo_name.clone()).get().await {
-            Err(e) => {
-                aprintln!(
-                    "Repo {username}/{repo_name} is unavailable", 
-                    username = external_username.clone(), 
-                    repo_name = external_repo_name.clone()
-                );
-                if *cli_only {
-                    return Err(cli_only_error);
+        aprintln!("{owner}/{name:<width$}  {result_display}", name = gh_repo.name, width = name_width);
+    }
+    if failed_count > 0 {
+        return_cmd_err!("{action_display} failed for {failed_count} of {repos_count} repos");
+    }
+
+    aprintln!("{hr}\n\nSUCCESS! Applied {action_display} to {repos_count} repos.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
+
+// Git work trees & bare repos under dir, nested repos (submodules, vendored) are not searched
+fn git_repos_find_sync(dir: &std::path::Path, depth: usize, repo_paths: &mut Vec<std::path::PathBuf>) {
+    if dir.join(".git").exists() || (dir.join("HEAD").is_file() && dir.join("objects").is_dir()) {
+        repo_paths.push(dir.to_path_buf());
+        return;
+    }
+    if depth == 0 { return; }
+    if let Ok(read_dir) = std::fs::read_dir(dir) {
+        for entry in read_dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || name == "node_modules" || name == "target" { continue; }
+            if let Ok(t) = entry.file_type() {
+                if t.is_dir() {
+                    git_repos_find_sync(&entry.path(), depth - 1, repo_paths);
                 }
-                continue;
-            }
-            Ok(repo) => {
-                return Ok((external_username, external_repo_name, repo));
             }
         }
     }
 }
 
-async fn get_is_public(public_raw: &bool, default_is_public: bool, cli_only: &bool) -> bool {
-    match *public_raw {
-        true => *public_raw,
-        false => match *cli_only {
-            true => false,
-            false => prompt_bool("Make it public?", Some(default_is_public)).await
+// Rewrites remotes of clones under clones_dir, of current checkout without it
+async fn retarget_local_clones(
+    clones_dir_raw_opt: &Option<&str>,
+    owner: &str,
+    repo_name: &str,
+    new_owner: &str,
+    new_repo_name: &str,
+) -> GhRsResult<usize> {
+    let mut repo_paths = vec![];
+    match clones_dir_raw_opt {
+        Some(clones_dir_raw) => {
+            let clones_dir = path!("{clones_dir_raw}");
+            if !clones_dir.is_dir().await {
+                return_cmd_err!("Directory {clones_dir_raw} not found");
+            }
+            git_repos_find_sync(clones_dir.as_ref(), 4, &mut repo_paths);
+        },
+        None => repo_paths.push(std::path::PathBuf::from(".")),
+    }
+    let mut clones_count = 0;
+    for repo_path in repo_paths {
+        let repo = match git2::Repository::open(&repo_path) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        let remote_names = match git2_remotes_retarget(&repo, owner, repo_name, new_owner, new_repo_name) {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to update remotes of {path_display}, error: {e}", path_display = repo_path.display()),
+        };
+        if !remote_names.is_empty() {
+            aprintln!(
+                "Updated remotes of {path_display}: {remotes}",
+                path_display = repo_path.display(),
+                remotes = remote_names.join(", "),
+            );
+            clones_count += 1;
         }
     }
+    Ok(clones_count)
 }
 
-fn get_readme_text(
-    username: &str,
+// Copy with history, labels, milestones, releases & wiki, original repo is kept
+async fn transfer_mirror(
+    octocrab_client: &octocrab::Octocrab,
+    token: &str,
+    user: &octocrab::models::Author,
+    owner: &str,
     repo_name: &str,
-    repo_description: &str,
-) -> String {
-    format!("# {repo_name}
-**{repo_description}**
-[GitHub](https://github.com/{username}/{repo_name})
-
-## Motivation
-Add motivation here
-
-## Installation
-`add_installation_command_here`
-
-## Usage
-Add usage & examples here
+    gh_repo: &octocrab::models::Repository,
+    new_owner: &str,
+    new_repo_name: &str,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let ssh_private_key = match get_or_create_ssh_key(octocrab_client).await {
+        Ok(k) => k,
+        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
+    };
+    let mirror_path = path::PathBuf::from(std::env::temp_dir()).join(format!("gh.rs-{new_repo_name}.git"));
+    if mirror_path.exists().await {
+        let _ = fs::remove_dir_all(&mirror_path).await;
+    }
+    let _ = fork_mirror(
+        octocrab_client,
+        token,
+        user,
+        &ssh_private_key,
+        owner,
+        repo_name,
+        gh_repo,
+        new_owner,
+        new_repo_name,
+        &!gh_repo.private.unwrap_or(false),
+        &mirror_path,
+        &ForkCopyOptions {
+            labels: true,
+            milestones: true,
+            releases: true,
+            wiki: true,
+        },
+        &OctocrabReposCreateOptions::default(),
+        &rewrite::RewriteOptions::default(),
+        cli_only,
+    ).await?;
+    let _ = match fs::remove_dir_all(&mirror_path).await {
+        Err(e) => return_cmd_err!(
+            "Failed to remove directory \"{path_display}\", error: {e}",
+            path_display = path_display(&mirror_path)
+        ),
+        Ok(r) => r,
+    };
+    Ok(())
+}
 
-## Development
-### TODO:
-- [x] Create project & repo
-- [ ] Fill out that TODO list
-- [ ] Fill out the Motivation section
-- [ ] Fill out the Installation section
-- [ ] Fill out the Usage section
+async fn run_repo_transfer_cmd(
+    repo_raw: &str,
+    new_owner_raw: &str,
+    new_repo_name_raw_opt: &Option<&str>,
+    clones_dir_raw_opt: &Option<&str>,
+    mirror_fallback: &bool,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    if !re_username().is_match(new_owner_raw) {
+        return_cmd_err!("Invalid new owner \"{new_owner_raw}\"");
+    }
+    let new_owner = new_owner_raw.to_string();
+    let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, gh_repo) = get_external_path(&octocrab_client, &Some(repo_raw), cli_only).await?;
+    let new_repo_name = match new_repo_name_raw_opt {
+        Some(new_repo_name_raw) if re_repo_name().is_match(new_repo_name_raw) => new_repo_name_raw.to_string(),
+        Some(new_repo_name_raw) => return_cmd_err!("Invalid new repo name \"{new_repo_name_raw}\""),
+        None => repo_name.clone(),
+    };
+    if octocrab_client.repos(new_owner.clone(), new_repo_name.clone()).get().await.is_ok() {
+        return_cmd_err!("Repo {new_owner}/{new_repo_name} already exists");
+    }
 
-## Credits:
-- Local and remote (online) repo created from command line by [gh.rs]({gh_rs_github_url})
+    match octocrab_repos_transfer(&octocrab_client, &owner, &repo_name, &new_owner, &new_repo_name).await {
+        Ok(()) => {
+            aprintln!("Transferring {owner}/{repo_name} to {new_owner}/{new_repo_name}...");
+            // transfers are async, into user accounts they wait for acceptance
+            let retry_timeout_duration = tokio::time::Duration::from_secs(2);
+            let mut is_transferred = false;
+            for _ in 0..30 {
+                tokio::time::sleep(retry_timeout_duration).await;
+                if let Ok(new_gh_repo) = octocrab_client.repos(new_owner.clone(), new_repo_name.clone()).get().await {
+                    let new_gh_repo_owner = new_gh_repo.owner.map(|o| o.login).unwrap_or_default();
+                    if new_gh_repo_owner.eq_ignore_ascii_case(&new_owner) {
+                        is_transferred = true;
+                        break;
+                    }
+                }
+            }
+            if !is_transferred {
+                return_cmd_err!(
+                    "Transfer to {new_owner} is not completed yet, user accounts have to accept it by email, local remotes are not changed"
+                );
+            }
+        },
+        Err(transfer_error) => {
+            aprintln!("Transfer is not permitted, error: {transfer_error}");
+            let use_mirror = *mirror_fallback || (!*cli_only && prompt_bool(
+                &format!("Mirror {owner}/{repo_name} to {new_owner}/{new_repo_name} instead (original is kept)?"),
+                Some(false),
+            ).await);
+            if !use_mirror {
+                return_cmd_err!("Failed to transfer {owner}/{repo_name}, set --mirror-fallback to mirror it instead");
+            }
+            transfer_mirror(&octocrab_client, &token, &user, &owner, &repo_name, &gh_repo, &new_owner, &new_repo_name, cli_only).await?;
+            aprintln!("Mirrored {owner}/{repo_name} to {new_owner}/{new_repo_name}, archive or delete the original with \"repo archive\" or \"repo delete\"");
+        },
+    }
 
+    let clones_count = retarget_local_clones(clones_dir_raw_opt, &owner, &repo_name, &new_owner, &new_repo_name).await?;
+    if clones_count == 0 {
+        aprintln!("No local clones of {owner}/{repo_name} found");
+    }
 
-",
-        gh_rs_github_url = get_gh_rs_github_url(),
-    )
+    aprintln!("{hr}\n\nSUCCESS! Moved {repo_name} repo to {new_owner}/{new_repo_name}.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
 }
 
-
-async fn run_new_cmd(
-    repo_name_raw_opt: &Option<&str>,
-    repo_description_raw_opt: &Option<&str>,
-    public_raw: &bool,
+async fn run_collab_add_cmd(
+    login_raw: &str,
+    repo_raw_opt: &Option<&str>,
+    permission_raw_opt: &Option<&str>,
     token_raw_opt: &Option<&str>,
     cli_only: &bool,
 ) -> GhRsResult<()> {
-    let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
-    let username = user.login.clone();
-    let repo_name = get_repo_name(&octocrab_client, &username, repo_name_raw_opt, None, cli_only).await?;
-    let repo_description = get_repo_description(repo_description_raw_opt, cli_only).await?;
-    let repo_public = get_is_public(public_raw, false, cli_only).await;
+    let permission = permission_raw_opt.unwrap_or("push");
+    let invites = get_invites(&vec![format!("{login_raw}:{permission}").as_str()])?;
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, _gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    apply_invites(&octocrab_client, &owner, &repo_name, &invites).await?;
 
-    let repo_path = path!("./{repo_name}");
-    let _ = match fs::create_dir(&repo_path).await {
-        Err(e) => return_cmd_err!("Failed to create directory ./{repo_name}, error: {e}"),
-        Ok(r) => r,
-    };
-    let branch_name = "main";
-    let repo = match git2::Repository::init_opts(
-        &repo_path, 
-        git2::RepositoryInitOptions::new()
-            .initial_head("main")
-    ) {
-        Ok(r) => r,
-        Err(e) => return_cmd_err!(
-            "Failed to init repo in \"{repo_path_display}\", error: {e}",
-            repo_path_display = path_display(&repo_path)
-        ),
-    };
+    aprintln!("{hr}\n\nSUCCESS! Shared {owner}/{repo_name} repo with {login_raw}.\nHappy hacking & have a nice day :)", hr = get_hr());
+    Ok(())
+}
 
-    let readme_path = repo_path.join("README.md");
-    let _ = match fs::write(&readme_path, get_readme_text(&username, &repo_name, &repo_description).as_bytes()).await {
+// Pending invitation is revoked, collaborator is removed otherwise
+async fn run_collab_remove_cmd(
+    login_raw: &str,
+    repo_raw_opt: &Option<&str>,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, _gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    let invitations = match octocrab_repo_invitations_list(&octocrab_client, &owner, &repo_name).await {
         Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to write README.md, error: {e}"),
+        Err(e) => return_cmd_err!("Failed to list invitations of {owner}/{repo_name}, error: {e}"),
     };
-
-    let sig = match get_github_signature_from_machine().await {
-        Some(sig_found) => sig_found,
+    let invitation_opt = invitations.iter().find(|i| {
+        i.invitee.as_ref().map(|invitee| invitee.login.eq_ignore_ascii_case(login_raw)).unwrap_or(false)
+    });
+    match invitation_opt {
+        Some(invitation) => {
+            let _ = match octocrab_repo_invitations_delete(&octocrab_client, &owner, &repo_name, invitation.id).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to revoke invitation of {login_raw}, error: {e}"),
+            };
+            aprintln!("Revoked invitation of {login_raw}");
+        },
         None => {
-            if user.email.is_some() {
-                match git2::Signature::now(
-                    user.login.clone().as_str(), 
-                    user.email.clone().expect("must be some").as_str()
-                ) {
-                    Ok(s) => s,
-                    Err(e) => return_cmd_err!("Failed to create signature, error: {e}"),
-                }
-            } else {
-                return_cmd_err!("Failed to find signature");
-            }
+            let _ = match octocrab_collaborators_remove(&octocrab_client, &owner, &repo_name, login_raw).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to remove {login_raw}, error: {e}"),
+            };
+            aprintln!("Removed {login_raw}");
         },
-    };
-    let _ = match git2_add_all_and_commit(
-        &repo, 
-        &sig, 
-        ["."], 
-        "Initial commit [gh.rs]",
-    ).await {
-        Err(e) => return_cmd_err!("Failed to commit, error: {e}"),
+    }
+
+    aprintln!("{hr}\n\nSUCCESS! Removed {login_raw} from {owner}/{repo_name} repo.\nHave a nice day :)", hr = get_hr());
+    Ok(())
+}
+
+async fn run_collab_list_cmd(
+    repo_raw_opt: &Option<&str>,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let (owner, repo_name, _gh_repo) = get_target_repo(&octocrab_client, repo_raw_opt, cli_only).await?;
+    let collaborators = match octocrab_collaborators_list(&octocrab_client, &owner, &repo_name, "all").await {
         Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to list collaborators of {owner}/{repo_name}, error: {e}"),
     };
-    let ssh_private_key = match get_or_create_ssh_key(&octocrab_client).await {
-        Ok(k) => k,
-        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
-    };
-    let gh_repo: octocrab::models::Repository = match octocrab_repos_create(
-        &octocrab_client,
-        &repo_public,
-        &repo_name,
-        &repo_description,
-    ).await {
+    let invitations = match octocrab_repo_invitations_list(&octocrab_client, &owner, &repo_name).await {
         Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to create repo, error: {e}"),
+        Err(e) => return_cmd_err!("Failed to list invitations of {owner}/{repo_name}, error: {e}"),
     };
-    
-    let remote_name = "origin";
-    let remote_url = format!("https://github.com/{username}/{repo_name}.git");
-    let _ = match git2_push(
-        &repo,
-        &remote_name,
-        &remote_url,
-        &false,
+    let mut rows: Vec<Vec<String>> = collaborators.into_iter()
+        .map(|c| vec![c.login, settings::permission_normalize(c.role_name.as_deref().unwrap_or("")), "".to_string()])
+        .collect();
+    for invitation in invitations {
+        let login = invitation.invitee.map(|i| i.login).unwrap_or("(email)".to_string());
+        rows.push(vec![login, settings::permission_normalize(&invitation.permissions), "invited".to_string()]);
+    }
+    for line in format_table(&["USER", "PERMISSION", "STATUS"], &rows) {
+        aprintln!("{line}");
+    }
+    Ok(())
+}
 
-        user.login.clone().as_str(), 
-        &token, 
-        &ssh_private_key,
-    ) {
-        Err(e) => return_cmd_err!("Failed to push, error: {e}"),
-        Ok(r) => r,
-    };
-    
-    let _ = match git2_set_branch_upstream(&repo, branch_name, remote_name) {
+// Invitation by id or by repo it's for
+fn find_invitation<'i>(
+    invitations: &'i [OctocrabModelInvitation],
+    invitation_raw: &str,
+) -> Option<&'i OctocrabModelInvitation> {
+    if let Ok(invitation_id) = invitation_raw.trim().parse::<u64>() {
+        return invitations.iter().find(|i| i.id == invitation_id);
+    }
+    let (owner, repo_name) = resolve_github_path(invitation_raw.trim())?;
+    let full_name = format!("{owner}/{repo_name}");
+    invitations.iter().find(|i| {
+        i.repository.as_ref().map(|r| r.full_name.eq_ignore_ascii_case(&full_name)).unwrap_or(false)
+    })
+}
+
+async fn run_invites_list_cmd(
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let invitations = match octocrab_user_invitations_list(&octocrab_client).await {
         Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to set \"{branch_name}\" branch upstream, error: {e}"),
+        Err(e) => return_cmd_err!("Failed to list invitations, error: {e}"),
     };
-
-    aprintln!("{hr}\n\nSUCCESS! Created {repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    if invitations.is_empty() {
+        aprintln!("No pending invitations");
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = invitations.into_iter().map(|i| vec![
+        i.id.to_string(),
+        i.repository.map(|r| r.full_name).unwrap_or_default(),
+        settings::permission_normalize(&i.permissions),
+        i.inviter.map(|inviter| inviter.login).unwrap_or_default(),
+    ]).collect();
+    for line in format_table(&["ID", "REPO", "PERMISSION", "INVITER"], &rows) {
+        aprintln!("{line}");
+    }
     Ok(())
 }
 
-
-async fn run_publish_cmd(
-    repo_description_raw_opt: &Option<&str>,
-    public_raw: &bool,
+async fn run_invites_respond_cmd(
+    invitation_raw: &str,
+    accept: &bool,
     token_raw_opt: &Option<&str>,
     cli_only: &bool,
 ) -> GhRsResult<()> {
-    let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
-    let username = user.login.clone();
-    
-    let repo_description = get_repo_description(repo_description_raw_opt, cli_only).await?;
-    let repo_public = get_is_public(public_raw, false, cli_only).await;
-    
-    let repo_path = path!("./").canonicalize().await.expect("must be able to canonicalize");
-    let repo_name = repo_path.file_name().expect("must be able to get directory name").to_str().expect("must be able decode directory name as utf8");
-    let branch_name = "main";
-    let repo = match repo_path.join(".git").is_dir().await {
-        true => match git2::Repository::open(&repo_path) {
-            Ok(r) => r,
-            Err(e) => return_cmd_err!(
-                "Failed to open repo from \"{repo_path_display}\", error: {e}",
-                repo_path_display = path_display(&repo_path)
-            ),
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let invitations = match octocrab_user_invitations_list(&octocrab_client).await {
+        Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to list invitations, error: {e}"),
+    };
+    let invitation = match find_invitation(&invitations, invitation_raw) {
+        Some(i) => i,
+        None => return_cmd_err!("Invitation \"{invitation_raw}\" not found, see \"invites list\""),
+    };
+    let repo_full_name = invitation.repository.as_ref().map(|r| r.full_name.clone()).unwrap_or(invitation.id.to_string());
+    match *accept {
+        true => {
+            let _ = match octocrab_user_invitations_accept(&octocrab_client, invitation.id).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to accept invitation to {repo_full_name}, error: {e}"),
+            };
+            aprintln!("{hr}\n\nSUCCESS! Joined {repo_full_name} repo, clone it with \"clone -e {repo_full_name}\".\nHappy hacking & have a nice day :)", hr = get_hr());
         },
-        false => match git2::Repository::init_opts(
-            &repo_path, 
-            git2::RepositoryInitOptions::new()
-                .initial_head(branch_name)
-        ) {
-            Ok(r) => r,
-            Err(e) => return_cmd_err!(
-                "Failed to init repo in \"{repo_path_display}\", error: {e}",
-                repo_path_display = path_display(&repo_path)
-            ),
+        false => {
+            let _ = match octocrab_user_invitations_decline(&octocrab_client, invitation.id).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to decline invitation to {repo_full_name}, error: {e}"),
+            };
+            aprintln!("{hr}\n\nSUCCESS! Declined invitation to {repo_full_name} repo.\nHave a nice day :)", hr = get_hr());
         },
-    };
+    }
+    Ok(())
+}
 
-    let readme_path = repo_path.join("README.md");
-    if !readme_path.exists().await {
-        let _ = match fs::write(&readme_path, get_readme_text(&username, &repo_name, &repo_description).as_bytes()).await {
+// Org if --org is set, environment of repo if --env is set, repo otherwise
+async fn get_actions_scope(
+    octocrab_client: &octocrab::Octocrab,
+    repo_raw_opt: &Option<&str>,
+    environment_raw_opt: &Option<&str>,
+    org_raw_opt: &Option<&str>,
+    visibility_raw_opt: &Option<&str>,
+    selected_repos_raw: &Vec<&str>,
+    cli_only: &bool,
+) -> GhRsResult<actions::ActionsScope> {
+    let org = match org_raw_opt {
+        Some(org) => org.to_string(),
+        None => {
+            let (owner, repo_name, _gh_repo) = get_target_repo(octocrab_client, repo_raw_opt, cli_only).await?;
+            return Ok(match environment_raw_opt {
+                Some(environment) => actions::ActionsScope::Environment { owner, repo_name, environment: environment.to_string() },
+                None => actions::ActionsScope::Repo { owner, repo_name },
+            });
+        },
+    };
+    let visibility = match (visibility_raw_opt, selected_repos_raw.is_empty()) {
+        (Some(visibility), _) => visibility.to_string(),
+        (None, true) => "private".to_string(),
+        (None, false) => "selected".to_string(),
+    };
+    if visibility != "selected" && !selected_repos_raw.is_empty() {
+        return_cmd_err!("--selected-repo requires \"selected\" visibility, got \"{visibility}\"");
+    }
+    let mut selected_repository_ids = vec![];
+    for repo_name in selected_repos_raw {
+        let gh_repo = match octocrab_client.repos(&org, *repo_name).get().await {
             Ok(r) => r,
-            Err(e) => return_cmd_err!("Failed to write README.md, error: {e}"),
+            Err(e) => return_cmd_err!("Failed to get {org}/{repo_name} repo, error: {e}"),
         };
+        selected_repository_ids.push(*gh_repo.id);
     }
-    
-    let sig = match get_github_signature_from_machine().await {
-        Some(sig_found) => sig_found,
-        None => {
-            if user.email.is_some() {
-                match git2::Signature::now(
-                    user.login.clone().as_str(), 
-                    user.email.clone().expect("must be some").as_str()
-                ) {
-                    Ok(s) => s,
-                    Err(e) => return_cmd_err!("Failed to create signature, error: {e}"),
-                }
-            } else {
-                return_cmd_err!("Failed to find signature");
+    Ok(actions::ActionsScope::Org { org, visibility, selected_repository_ids })
+}
+
+// From file, piped stdin or prompt (not echoed for secrets), piped values lose trailing newline of echo
+async fn get_actions_value(
+    kind: &str,
+    name: &str,
+    file_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<String> {
+    let value = match file_raw_opt {
+        Some(file_raw) => {
+            let file_path = path!("{file_raw}");
+            match fs::read_to_string(&file_path).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to read {file_raw}, error: {e}"),
             }
         },
+        None => match stdin_read_piped().await {
+            Ok(Some(text)) => text.strip_suffix('\n').map(|t| t.strip_suffix('\r').unwrap_or(t)).unwrap_or(&text).to_string(),
+            Ok(None) => match (cli_only, kind) {
+                (true, _) => return Err(GhRsError::CliOnly(format!("value of {name} (--file or stdin)"))),
+                (false, "secrets") => prompt_hidden(&format!("Value of {name} (hidden)")).await,
+                (false, _) => prompt(&format!("Value of {name}"), None).await.trim_end_matches(['\r', '\n']).to_string(),
+            },
+            Err(e) => return_cmd_err!("Failed to read value of {name} from stdin, error: {e}"),
+        },
     };
-    let _ = match git2_add_all_and_commit(
-        &repo, 
-        &sig, 
-        ["."], 
-        "Initial commit [gh.rs]",
-    ).await {
-        Err(e) => return_cmd_err!("Failed to commit, error: {e}"),
-        Ok(r) => r,
-    };
-    let ssh_private_key = match get_or_create_ssh_key(&octocrab_client).await {
-        Ok(k) => k,
-        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
-    };
-    let gh_repo: octocrab::models::Repository = match octocrab_repos_create(
-        &octocrab_client,
-        &repo_public,
-        &repo_name,
-        &repo_description,
-    ).await {
+    Ok(value)
+}
+
+// Secrets are sealed with public key of scope, variables are sent as-is
+async fn apply_actions_values(
+    octocrab_client: &octocrab::Octocrab,
+    scope: &actions::ActionsScope,
+    kind: &str,
+    values: &[(String, String)],
+) -> GhRsResult<()> {
+    for (name, value) in values {
+        if !actions::name_is_valid(name) {
+            return_cmd_err!("Invalid name \"{name}\", only letters, digits & underscores are allowed, not starting with a digit or GITHUB_");
+        }
+        if value.is_empty() {
+            return_cmd_err!("Value of {name} is empty");
+        }
+        if kind == "secrets" && value.len() > actions::secret_max_size {
+            return_cmd_err!("Secret {name} is {size}, GitHub limit is {limit}", size = format_size(value.len()), limit = format_size(actions::secret_max_size));
+        }
+    }
+    if kind == "secrets" {
+        let public_key = match octocrab_actions_public_key(octocrab_client, scope).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to get public key of {scope}, error: {e}", scope = scope.describe()),
+        };
+        for (name, value) in values {
+            let encrypted_value = match actions::seal(&public_key.key, value) {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to seal {name}, {e}"),
+            };
+            let _ = match octocrab_actions_secret_put(octocrab_client, scope, name, &encrypted_value, &public_key.key_id).await {
+                Ok(r) => r,
+                Err(e) => return_cmd_err!("Failed to set secret {name}, error: {e}"),
+            };
+            aprintln!("Set secret {name}");
+        }
+        return Ok(());
+    }
+    let existing = match octocrab_actions_list(octocrab_client, scope, kind).await {
         Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to create repo, error: {e}"),
+        Err(e) => return_cmd_err!("Failed to list variables of {scope}, error: {e}", scope = scope.describe()),
     };
-    
-    let remote_name = "origin";
-    let remote_url = format!("https://github.com/{username}/{repo_name}.git");
-    let _ = match git2_push(
-        &repo,
-        &remote_name,
-        &remote_url,
-        &false,
+    for (name, value) in values {
+        let exists = existing.iter().any(|v| v.name.eq_ignore_ascii_case(name));
+        let _ = match octocrab_actions_variable_put(octocrab_client, scope, name, value, exists).await {
+            Ok(r) => r,
+            Err(e) => return_cmd_err!("Failed to set variable {name}, error: {e}"),
+        };
+        aprintln!("Set variable {name}");
+    }
+    Ok(())
+}
+
+// kind is "secrets" or "variables"
+async fn run_actions_set_cmd(
+    kind: &str,
+    name_raw: &str,
+    file_raw_opt: &Option<&str>,
+    repo_raw_opt: &Option<&str>,
+    environment_raw_opt: &Option<&str>,
+    org_raw_opt: &Option<&str>,
+    visibility_raw_opt: &Option<&str>,
+    selected_repos_raw: &Vec<&str>,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let value = get_actions_value(kind, name_raw, file_raw_opt, cli_only).await?;
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let scope = get_actions_scope(&octocrab_client, repo_raw_opt, environment_raw_opt, org_raw_opt, visibility_raw_opt, selected_repos_raw, cli_only).await?;
+    apply_actions_values(&octocrab_client, &scope, kind, &[(name_raw.to_string(), value)]).await?;
+
+    aprintln!("{hr}\n\nSUCCESS! Set {name_raw} of {scope}.\nHappy hacking & have a nice day :)", hr = get_hr(), scope = scope.describe());
+    Ok(())
+}
 
-        user.login.clone().as_str(), 
-        &token, 
-        &ssh_private_key,
-    ) {
-        Err(e) => return_cmd_err!("Failed to push, error: {e}"),
+// Every entry of dotenv file
+async fn run_actions_import_cmd(
+    kind: &str,
+    env_file_raw: &str,
+    repo_raw_opt: &Option<&str>,
+    environment_raw_opt: &Option<&str>,
+    org_raw_opt: &Option<&str>,
+    visibility_raw_opt: &Option<&str>,
+    selected_repos_raw: &Vec<&str>,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let env_file_path = path!("{env_file_raw}");
+    let env_file_text = match fs::read_to_string(&env_file_path).await {
         Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to read {env_file_raw}, error: {e}"),
     };
-    let _ = match git2_set_branch_upstream(&repo, branch_name, remote_name) {
+    let values = match actions::dotenv_parse(&env_file_text) {
         Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to set \"{branch_name}\" branch upstream, error: {e}"),
+        Err(e) => return_cmd_err!("Invalid {env_file_raw}, {e}"),
     };
+    if values.is_empty() {
+        return_cmd_err!("No KEY=VALUE entries in {env_file_raw}");
+    }
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let scope = get_actions_scope(&octocrab_client, repo_raw_opt, environment_raw_opt, org_raw_opt, visibility_raw_opt, selected_repos_raw, cli_only).await?;
+    apply_actions_values(&octocrab_client, &scope, kind, &values).await?;
 
-    aprintln!("{hr}\n\nSUCCESS! Published {repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    aprintln!(
+        "{hr}\n\nSUCCESS! Set {count} {kind} of {scope} from {env_file_raw}.\nHappy hacking & have a nice day :)",
+        hr = get_hr(),
+        count = values.len(),
+        scope = scope.describe(),
+    );
     Ok(())
 }
 
-async fn run_clone_cmd(
-    external_path_raw_opt: &Option<&str>,
+async fn run_actions_list_cmd(
+    kind: &str,
+    repo_raw_opt: &Option<&str>,
+    environment_raw_opt: &Option<&str>,
+    org_raw_opt: &Option<&str>,
     token_raw_opt: &Option<&str>,
     cli_only: &bool,
 ) -> GhRsResult<()> {
-    let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
-    let username = user.login.clone();
-    let (external_username, external_repo_name, external_gh_repo) = get_external_path(&octocrab_client, external_path_raw_opt, cli_only).await?;
-    
-    let path = path!("./{external_repo_name}");
-    let _ = match fs::create_dir(path.clone()).await {
-        Err(e) => return_cmd_err!("Failed to create directory ./{external_repo_name}, error: {e}"),
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let scope = get_actions_scope(&octocrab_client, repo_raw_opt, environment_raw_opt, org_raw_opt, &None, &vec![], cli_only).await?;
+    let entries = match octocrab_actions_list(&octocrab_client, &scope, kind).await {
         Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to list {kind} of {scope}, error: {e}", scope = scope.describe()),
     };
-    
-    let ssh_private_key = match get_or_create_ssh_key(&octocrab_client).await {
-        Ok(k) => k,
-        Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
-    };
-    
-    let repo_clone_url = format!("https://github.com/{external_username}/{external_repo_name}.git");
-    let repo = match git2_clone(
-        &repo_clone_url,
-        &path,
-        &false,
+    if entries.is_empty() {
+        aprintln!("No {kind} in {scope}", scope = scope.describe());
+        return Ok(());
+    }
+    let is_org = org_raw_opt.is_some();
+    let mut header = vec!["NAME"];
+    if kind == "variables" {
+        header.push("VALUE");
+    }
+    if is_org {
+        header.push("VISIBILITY");
+    }
+    header.push("UPDATED");
+    let rows: Vec<Vec<String>> = entries.into_iter().map(|entry| {
+        let mut row = vec![entry.name];
+        if kind == "variables" {
+            row.push(entry.value.unwrap_or_default());
+        }
+        if is_org {
+            row.push(entry.visibility.unwrap_or_default());
+        }
+        row.push(entry.updated_at.unwrap_or_default());
+        row
+    }).collect();
+    for line in format_table(&header, &rows) {
+        aprintln!("{line}");
+    }
+    Ok(())
+}
 
-        user.login.clone().as_str(), 
-        &token, 
-        &ssh_private_key,
-    ) {
+async fn run_actions_delete_cmd(
+    kind: &str,
+    name_raw: &str,
+    repo_raw_opt: &Option<&str>,
+    environment_raw_opt: &Option<&str>,
+    org_raw_opt: &Option<&str>,
+    token_raw_opt: &Option<&str>,
+    cli_only: &bool,
+) -> GhRsResult<()> {
+    let (octocrab_client, _token, _user) = get_auth(token_raw_opt, cli_only).await?;
+    let scope = get_actions_scope(&octocrab_client, repo_raw_opt, environment_raw_opt, org_raw_opt, &None, &vec![], cli_only).await?;
+    let _ = match octocrab_actions_delete(&octocrab_client, &scope, kind, name_raw).await {
         Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to clone repo, error: {e}"),
+        Err(e) => return_cmd_err!("Failed to delete {name_raw} of {scope}, error: {e}", scope = scope.describe()),
     };
-    
-    aprintln!("{hr}\n\nSUCCESS! Cloned {external_repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+
+    aprintln!("{hr}\n\nSUCCESS! Deleted {name_raw} of {scope}.\nHave a nice day :)", hr = get_hr(), scope = scope.describe());
     Ok(())
 }
 
-async fn run_fork_cmd(
-    external_path_raw_opt: &Option<&str>,
+async fn run_import_cmd(
+    source_raw_opt: &Option<&str>,
     repo_name_raw_opt: &Option<&str>,
+    repo_description_raw_opt: &Option<&str>,
     public_raw: &bool,
     token_raw_opt: &Option<&str>,
     cli_only: &bool,
 ) -> GhRsResult<()> {
     let (octocrab_client, token, user) = get_auth(token_raw_opt, cli_only).await?;
     let username = user.login.clone();
-    let (external_username, external_repo_name, external_gh_repo) = get_external_path(&octocrab_client, external_path_raw_opt, cli_only).await?;
+    let (source_url, source_repo_name) = get_import_source(source_raw_opt, cli_only).await?;
 
-    let repo_name = get_repo_name(&octocrab_client, &username, repo_name_raw_opt, Some(&external_repo_name), cli_only).await?;
+    let repo_name_default = Some(source_repo_name.as_str()).filter(|n| re_repo_name().is_match(n));
+    let repo_name = get_repo_name(&octocrab_client, &username, repo_name_raw_opt, repo_name_default, &true, &false, cli_only).await?;
+    let repo_description = get_repo_description(repo_description_raw_opt, None, cli_only).await?;
     let repo_public = get_is_public(public_raw, false, cli_only).await;
 
-
     let path = path!("./{repo_name}");
     let _ = match fs::create_dir(&path).await {
         Err(e) => return_cmd_err!("Failed to create directory ./{repo_name}, error: {e}"),
         Ok(r) => r,
     };
-    
+
     let ssh_private_key = match get_or_create_ssh_key(&octocrab_client).await {
         Ok(k) => k,
         Err(e) => return_cmd_err!("Failed to create ssh key, error: {e}"),
     };
 
-    let external_url = format!("https://github.com/{external_username}/{external_repo_name}.git");
-    let external_repo = match git2_clone(
-        &external_url,
-        &path,
-        &true,
-
-        user.login.clone().as_str(), 
-        &token, 
-        &ssh_private_key,
-    ) {
+    // source may be anywhere, so credentials come from git's own helpers & ssh-agent
+    let source_repo = match git2_clone_with_credential_helpers(&source_url, &path, &true) {
         Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to clone repo, error: {e}"),
+        Err(e) => return_cmd_err!("Failed to clone {source_url}, error: {e}"),
     };
-    
-    let gh_repo: octocrab::models::Repository = match octocrab_repos_create(
+    let push_ref_names = git2_push_ref_names(&source_repo)?;
+    check_push_size(&source_repo, &push_ref_names.iter().map(|r| r.as_str()).collect::<Vec<&str>>(), cli_only).await?;
+
+    let _ = match octocrab_repos_create(
         &octocrab_client,
+        &None,
         &repo_public,
         &repo_name,
-        external_gh_repo.description.unwrap_or("".to_string()).as_str()
+        &repo_description,
+        &OctocrabReposCreateOptions::default(),
     ).await {
         Ok(r) => r,
         Err(e) => return_cmd_err!("Failed to create repo, error: {e}"),
     };
-    
+
     let remote_name = "origin";
     let remote_url = format!("https://github.com/{username}/{repo_name}.git");
+    if lfs::uses_lfs(&source_repo) {
+        let lfs_pointers = lfs::pointers_reachable(&source_repo, &["*"])?;
+        let fetched = match git2::Repository::open(source_url.strip_prefix("file://").unwrap_or(&source_url)) {
+            Ok(source_local_repo) => lfs::copy_from_repo(&source_repo, &source_local_repo, &lfs_pointers),
+            Err(_) => match lfs::endpoint_for_source(&source_repo, &source_url) {
+                Some(source_endpoint) => {
+                    let (source_username, source_password) = git2_credential_helpers_userpass(&source_endpoint)
+                        .unwrap_or_default();
+                    lfs::download(&source_repo, &source_endpoint, &lfs_pointers, &source_username, &source_password).await
+                },
+                None => return_cmd_err!("Failed to find LFS server for {source_url}"),
+            },
+        };
+        if let Err(e) = fetched {
+            return_cmd_err!("Failed to fetch LFS objects from {source_url}, error: {e}");
+        }
+        match git2_lfs_transfer(
+            &source_repo,
+            None,
+            &lfs::endpoint_for_remote_url(&remote_url).expect("github url"),
+            &["*"],
+
+            user.login.clone().as_str(),
+            &token,
+        ).await {
+            Ok(count) => aprintln!("Uploaded {count} LFS objects"),
+            Err(e) => return_cmd_err!("Failed to copy LFS objects, error: {e}"),
+        };
+    }
     let _ = match git2_push(
-        &external_repo,
-        &remote_name,
+        &source_repo,
+        remote_name,
         &remote_url,
         &true,
 
-        user.login.clone().as_str(), 
-        &token, 
+        user.login.clone().as_str(),
+        &token,
         &ssh_private_key,
     ) {
         Err(e) => return_cmd_err!("Failed to push, error: {e}"),
         Ok(r) => r,
     };
-    
-    let last_commit_id = external_repo.head().expect("has head")
-        .peel_to_commit().expect("has commit")
-        .id();
-    let _ = match fs::remove_dir_all(&path).await {
-        Err(e) => return_cmd_err!(
-            "Failed to remove directory \"{path_display}\", error: {e}", 
-            path_display = path_display(&path)
-        ),
+
+    let default_branch_name = match git2_default_branch_name(&source_repo) {
+        Ok(n) => n,
+        Err(e) => return_cmd_err!("Failed to get default branch name, error: {e}"),
+    };
+    // mirror push order decides default branch, set it explicitly
+    let _ = match octocrab_repos_update(
+        &octocrab_client,
+        &username,
+        &repo_name,
+        &serde_json::json!({ "default_branch": default_branch_name }),
+    ).await {
         Ok(r) => r,
+        Err(e) => return_cmd_err!("Failed to set \"{default_branch_name}\" as default branch, error: {e}"),
     };
 
-    let repo = match git2_clone(
-        &remote_url,
+    let repo = mirror_replace_with_clone(
+        source_repo,
         &path,
-        &false,
-
-        user.login.clone().as_str(), 
-        &token, 
-        &ssh_private_key,
-    ) {
-        Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to clone repo, error: {e}"),
-    };
-    
-    let _ = match git2_fetch_until_commit(
-        &repo,
-        &remote_name,
+        remote_name,
         &remote_url,
-        &last_commit_id,
-        &tokio::time::Duration::new(0, 500_000_000),
-        10,
 
-        user.login.clone().as_str(), 
-        &token, 
+        &user,
+        &token,
         &ssh_private_key,
-    ).await {
-        Ok(r) => r,
-        Err(e) => return_cmd_err!("Failed to fetch remote until latest commit, error: {e}"),
-    };
-
-    let default_branch_name = match git2_default_branch_name(&repo) {
-        Ok(n) => n,
-        Err(e) => return_cmd_err!("Failed to get default branch name, error: {e}"),
-    };
+    ).await?;
 
     let _ = match git2_set_branch_upstream(&repo, default_branch_name.as_str(), remote_name) {
         Ok(r) => r,
         Err(e) => return_cmd_err!("Failed to set \"{default_branch_name}\" branch upstream, error: {e}"),
     };
-    let remote_external = match repo.remote("external", &external_url) {
+    let remote_external = match repo.remote("external", &source_url) {
         Ok(r) => r,
         Err(e) => return_cmd_err!("Failed to create \"external\" remote, error: {e}"),
     };
 
-    aprintln!("{hr}\n\nSUCCESS! Forked {external_repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
+    aprintln!("{hr}\n\nSUCCESS! Imported {source_url} as {repo_name} repo.\nHappy hacking & have a nice day :)", hr = get_hr());
     Ok(())
 }
 
@@ -1400,6 +5976,121 @@ async fn async_main() {
     let cli_only_arg = arg!(cli_only: -c --"cli-only" "CLI-only mode, no prompts, will error if something is not specified, all bools will be set to false automatically")
         .visible_aliases(["co", "np", "no-prompt", "no-prompts", "no-prompting"]);
     let external_arg = arg!(external: -e --external <repo_url> "Set external repo url"); 
+    let with_labels_arg = arg!(with_labels: --"with-labels" "Copy labels from external repo");
+    let with_milestones_arg = arg!(with_milestones: --"with-milestones" "Copy milestones from external repo");
+    let with_releases_arg = arg!(with_releases: --"with-releases" "Copy releases with their assets from external repo");
+    let with_wiki_arg = arg!(with_wiki: --"with-wiki" "Copy wiki from external repo");
+    let topics_arg = arg!(topics: --topics <topics> "Set repo topics, comma separated, suggested from project manifest by default");
+    let remote_arg = arg!(remote: -r --remote <remote_name> "Set local name of GitHub remote, default \"origin\"");
+    let all_branches_arg = arg!(all_branches: --"all-branches" "Push all local branches, not only the current one");
+    let tags_arg = arg!(tags: --tags "Push all tags");
+    let interactive_arg = arg!(interactive: -i --interactive "Choose files for the commit from a tree of untracked & modified files");
+    let allow_secrets_arg = arg!(allow_secrets: --"allow-secrets" "Publish even if possible secrets (tokens, keys) are found");
+    let strip_path_arg = arg!(strip_path: --"strip-path" <path> "Remove path from the whole history before push, repeatable")
+        .action(clap::ArgAction::Append);
+    let strip_blobs_bigger_than_arg = arg!(strip_blobs_bigger_than: --"strip-blobs-bigger-than" <size> "Remove files bigger than size (e.g. 500K, 10M) from the whole history before push");
+    let mailmap_arg = arg!(mailmap: --mailmap <mailmap_file> "Rewrite authors & committers in the whole history with mailmap file before push");
+    let gitignore_arg = arg!(gitignore: --gitignore <template> "Add .gitignore from GitHub template (e.g. Rust, Node, Python), \"none\" to skip");
+    let license_arg = arg!(license: --license <spdx> "Add LICENSE by SPDX id (e.g. MIT, Apache-2.0), \"none\" to skip");
+    let template_arg = arg!(template: --template <template> "Render project template: directory path, name in ~/.config/gh.rs/templates or git url");
+    let template_var_arg = arg!(template_var: --"template-var" <name_value> "Set template variable as name=value, repeatable")
+        .action(clap::ArgAction::Append);
+    let from_template_arg = arg!(from_template: --"from-template" <template_repo> "Generate repo from GitHub template repository (owner/repo or url)")
+        .conflicts_with_all(["template", "gitignore", "license"]);
+    let include_all_branches_arg = arg!(include_all_branches: --"include-all-branches" "Copy all branches of GitHub template repository, not only default one")
+        .requires("from_template");
+    let org_arg = arg!(org: --org <org> "Create repo in organization instead of personal account");
+    let team_arg = arg!(team: --team <slug_permission> "Grant org team access as slug:permission (pull, triage, push, maintain, admin), repeatable")
+        .action(clap::ArgAction::Append)
+        .requires("org");
+    let invite_arg = arg!(invite: --invite <user_permission> "Invite collaborator as user:permission (pull, triage, push, maintain, admin), repeatable")
+        .action(clap::ArgAction::Append);
+    let profile_arg = arg!(profile: --profile <profile> "Use repo defaults from [profile.<profile>] section of gh.rs config");
+    let homepage_arg = arg!(homepage: --homepage <url> "Set repo homepage");
+    let issues_arg = arg!(issues: --issues <bool> "Enable issues")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let projects_arg = arg!(projects: --projects <bool> "Enable projects")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let wiki_arg = arg!(wiki: --wiki <bool> "Enable wiki")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let discussions_arg = arg!(discussions: --discussions <bool> "Enable discussions")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let is_template_arg = arg!(is_template: --"is-template" <bool> "Make repo a template repository")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let merge_methods_arg = arg!(merge_methods: --"merge-methods" <methods> "Allowed merge methods, comma separated: merge, squash, rebase");
+    let delete_branch_on_merge_arg = arg!(delete_branch_on_merge: --"delete-branch-on-merge" <bool> "Delete head branches after pull requests are merged")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let auto_merge_arg = arg!(auto_merge: --"auto-merge" <bool> "Allow auto-merge of pull requests")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let visibility_arg = arg!(visibility: --visibility <visibility> "Set visibility: public, private or internal (enterprise orgs)")
+        .conflicts_with("public");
+    let secret_scanning_arg = arg!(secret_scanning: --"secret-scanning" <bool> "Enable secret scanning")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let push_protection_arg = arg!(push_protection: --"push-protection" <bool> "Enable secret scanning push protection")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let repo_create_args = [
+        &profile_arg,
+        &homepage_arg,
+        &issues_arg,
+        &projects_arg,
+        &wiki_arg,
+        &discussions_arg,
+        &is_template_arg,
+        &merge_methods_arg,
+        &delete_branch_on_merge_arg,
+        &auto_merge_arg,
+        &visibility_arg,
+        &secret_scanning_arg,
+        &push_protection_arg,
+    ];
+    let protect_arg = arg!(protect: --protect "Protect default branch after push, policy is set in [protect] section of gh.rs config");
+    let repo_arg = arg!(repo: [repo] "GitHub repo (owner/repo or url), \"origin\" remote of current directory by default");
+    let branch_arg = arg!(branch: -b --branch <branch> "Protect branch instead of default one");
+    let source_arg = arg!(source: [url_or_path] "Git repo url (https://, ssh://, git@host:path, file://) or local path to import");
+    let default_branch_arg = arg!(default_branch: --"default-branch" <branch> "Set default branch");
+    let visibility_value_arg = arg!(visibility: <visibility> "New visibility: public, private or internal (enterprise orgs)");
+    let yes_arg = arg!(yes: -y --yes "Delete without typing repo name to confirm, with --cli-only");
+    let bulk_action_arg = arg!(action: <action> "Action to apply to selected repos")
+        .value_parser(bulk_actions);
+    let new_topic_arg = arg!(new_topic: --"new-topic" <topic> "Topic to add with add-topic action");
+    let owner_arg = arg!(owner: --owner <owner> "Select repos of user or org, authenticated user by default");
+    let name_regex_arg = arg!(name_regex: --"name-regex" <regex> "Select repos with names matching regex");
+    let pushed_before_arg = arg!(pushed_before: --"pushed-before" <date> "Select repos last pushed before date (YYYY-MM-DD)");
+    let fork_arg = arg!(fork: --fork <bool> "Select only forks (true) or only non-forks (false)")
+        .value_parser(clap::builder::BoolishValueParser::new());
+    let language_arg = arg!(language: --language <language> "Select repos with main language");
+    let topic_filter_arg = arg!(topic: --topic <topic> "Select repos having topic, repeatable")
+        .action(clap::ArgAction::Append);
+    let concurrency_arg = arg!(concurrency: --concurrency <count> "Repos changed at once")
+        .value_parser(clap::value_parser!(usize))
+        .default_value("4");
+    let dry_run_arg = arg!(dry_run: --"dry-run" "Only print selected repos, change nothing");
+    let bulk_yes_arg = arg!(yes: -y --yes "Apply without confirmation, delete also needs --cli-only");
+    let transfer_repo_arg = arg!(repo: <repo> "GitHub repo to transfer (owner/repo or url)");
+    let new_owner_arg = arg!(new_owner: <new_owner> "User or org to transfer repo to");
+    let new_name_arg = arg!(new_name: --"new-name" <name> "Rename repo while transferring");
+    let clones_dir_arg = arg!(clones_dir: --"clones-dir" <dir> "Rewrite remotes of local clones under directory, current checkout by default");
+    let mirror_fallback_arg = arg!(mirror_fallback: --"mirror-fallback" "Mirror repo to new owner when transfer is not permitted");
+    let collab_user_arg = arg!(user: <user> "GitHub username");
+    let permission_arg = arg!(permission: --permission <permission> "Access level, default push")
+        .value_parser(team_permissions);
+    let invitation_arg = arg!(invitation: <invitation> "Invitation id or repo it's for (owner/repo or url)");
+    let actions_name_arg = arg!(name: <name> "Name: letters, digits & underscores");
+    let actions_repo_arg = repo_arg.clone()
+        .conflicts_with("org");
+    let environment_arg = arg!(environment: --env <environment> "Use deployment environment of repo instead of repo itself")
+        .conflicts_with("org");
+    let actions_org_arg = arg!(org: --org <org> "Use organization instead of repo");
+    let org_visibility_arg = arg!(visibility: --visibility <visibility> "Org repos allowed to use it: all, private or selected, default private")
+        .value_parser(actions::org_visibilities)
+        .requires("org");
+    let selected_repo_arg = arg!(selected_repo: --"selected-repo" <repo_name> "Org repo allowed to use it, repeatable, implies selected visibility")
+        .action(clap::ArgAction::Append)
+        .requires("org");
+    let value_file_arg = arg!(file: -f --file <path> "Read value from file, stdin or prompt by default");
+    let env_file_arg = arg!(env_file: <env_file> "Dotenv file of KEY=VALUE lines");
+    let settings_file_arg = arg!(file: <file> "Settings file, .toml or .yaml");
+    let recurse_submodules_arg = arg!(recurse_submodules: --"recurse-submodules" "Fork GitHub-hosted submodules too & point .gitmodules to the forks");
     
     let after_help = format!(
         "gh.rs GitHub: {url}",
@@ -1411,6 +6102,19 @@ async fn async_main() {
             &name_arg,
             &description_arg,
             &public_arg,
+            &gitignore_arg,
+            &license_arg,
+            &template_arg,
+            &template_var_arg,
+            &from_template_arg,
+            &include_all_branches_arg,
+            &org_arg,
+            &team_arg,
+            &invite_arg,
+            &protect_arg,
+        ])
+        .args(repo_create_args)
+        .args([
             &token_arg,
             &cli_only_arg,
         ])
@@ -1418,8 +6122,26 @@ async fn async_main() {
     
     let publish_cmd = cmd!(-p -pub --publish "Publish current directory to GitHub")
         .args([
+            &name_arg,
             &description_arg,
+            &topics_arg,
             &public_arg,
+            &remote_arg,
+            &all_branches_arg,
+            &tags_arg,
+            &interactive_arg,
+            &allow_secrets_arg,
+            &strip_path_arg,
+            &strip_blobs_bigger_than_arg,
+            &mailmap_arg,
+            &gitignore_arg,
+            &license_arg,
+            &org_arg,
+            &team_arg,
+            &protect_arg,
+        ])
+        .args(repo_create_args)
+        .args([
             &token_arg,
             &cli_only_arg,
         ])
@@ -1438,11 +6160,290 @@ async fn async_main() {
             &external_arg,
             &name_arg,
             &public_arg,
+            &with_labels_arg,
+            &with_milestones_arg,
+            &with_releases_arg,
+            &with_wiki_arg,
+            &recurse_submodules_arg,
+            &strip_path_arg,
+            &strip_blobs_bigger_than_arg,
+            &mailmap_arg,
+            &org_arg,
+            &team_arg,
+            &invite_arg,
+        ])
+        .args(repo_create_args)
+        .args([
+            &token_arg,
+            &cli_only_arg,
+        ])
+        .after_help(&after_help);
+    
+    let import_cmd = cmd!(-i --import "Import repo from any git url or local path to GitHub")
+        .args([
+            &source_arg,
+            &name_arg,
+            &description_arg,
+            &public_arg,
+            &token_arg,
+            &cli_only_arg,
+        ])
+        .after_help(&after_help);
+    
+    let protect_cmd = cmd!(--protect "Protect branch of existing GitHub repo with rulesets or classic branch protection")
+        .args([
+            &repo_arg,
+            &branch_arg,
             &token_arg,
             &cli_only_arg,
         ])
         .after_help(&after_help);
     
+    let repo_cmd = cmd!(--repo "Manage existing GitHub repo")
+        .subcommand_required(true)
+        .subcommands([
+            cmd!(--edit "Edit repo description, homepage, topics, default branch & features")
+                .args([
+                    &repo_arg,
+                    &description_arg,
+                    &topics_arg.clone().help("Replace repo topics, comma separated"),
+                    &default_branch_arg,
+                    &homepage_arg,
+                    &issues_arg,
+                    &projects_arg,
+                    &wiki_arg,
+                    &discussions_arg,
+                    &is_template_arg,
+                    &merge_methods_arg,
+                    &delete_branch_on_merge_arg,
+                    &auto_merge_arg,
+                    &secret_scanning_arg,
+                    &push_protection_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--rename "Rename repo & update local remotes pointing to it")
+                .args([
+                    &repo_arg,
+                    &name_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--archive "Archive repo, making it read-only")
+                .args([
+                    &repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--unarchive "Unarchive repo")
+                .args([
+                    &repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--visibility "Change repo visibility")
+                .args([
+                    &visibility_value_arg,
+                    &repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--delete "Delete repo permanently")
+                .args([
+                    &repo_arg,
+                    &yes_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--transfer "Transfer repo to another user or org & rewrite local remotes")
+                .args([
+                    &transfer_repo_arg,
+                    &new_owner_arg,
+                    &new_name_arg,
+                    &clones_dir_arg,
+                    &mirror_fallback_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--bulk "Apply action to many repos selected by filters, previewed first")
+                .args([
+                    &bulk_action_arg,
+                    &new_topic_arg,
+                    &owner_arg,
+                    &name_regex_arg,
+                    &pushed_before_arg,
+                    &fork_arg,
+                    &language_arg,
+                    &topic_filter_arg,
+                    &concurrency_arg,
+                    &dry_run_arg,
+                    &bulk_yes_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+        ])
+        .after_help(&after_help);
+    
+    let collab_cmd = cmd!(--collab "Manage collaborators of GitHub repo")
+        .subcommand_required(true)
+        .subcommands([
+            cmd!(--add "Invite collaborator or change their permission")
+                .args([
+                    &collab_user_arg,
+                    &repo_arg,
+                    &permission_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--remove "Remove collaborator or revoke their invitation")
+                .args([
+                    &collab_user_arg,
+                    &repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--list "List collaborators & pending invitations")
+                .args([
+                    &repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+        ])
+        .after_help(&after_help);
+    
+    let invites_cmd = cmd!(--invites "Manage repo invitations of authenticated user")
+        .subcommand_required(true)
+        .subcommands([
+            cmd!(--list "List pending invitations")
+                .args([
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--accept "Accept invitation")
+                .args([
+                    &invitation_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--decline "Decline invitation")
+                .args([
+                    &invitation_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+        ])
+        .after_help(&after_help);
+    
+    let settings_cmd = cmd!(--settings "Manage settings of many GitHub repos as code")
+        .subcommand_required(true)
+        .subcommands([
+            cmd!(--plan "Show differences between settings file & live repos")
+                .args([
+                    &settings_file_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--apply "Apply settings file to live repos")
+                .args([
+                    &settings_file_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+        ])
+        .after_help(&after_help);
+    
+    let secret_cmd = cmd!(--secret "Manage GitHub Actions secrets of repo, environment or org")
+        .subcommand_required(true)
+        .subcommands([
+            cmd!(--set "Set encrypted secret from file, stdin or prompt")
+                .args([
+                    &actions_name_arg,
+                    &actions_repo_arg,
+                    &value_file_arg,
+                    &environment_arg,
+                    &actions_org_arg,
+                    &org_visibility_arg,
+                    &selected_repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--import "Set encrypted secrets from dotenv file")
+                .args([
+                    &env_file_arg,
+                    &actions_repo_arg,
+                    &environment_arg,
+                    &actions_org_arg,
+                    &org_visibility_arg,
+                    &selected_repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--list "List secrets")
+                .args([
+                    &actions_repo_arg,
+                    &environment_arg,
+                    &actions_org_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--delete "Delete secret")
+                .args([
+                    &actions_name_arg,
+                    &actions_repo_arg,
+                    &environment_arg,
+                    &actions_org_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+        ])
+        .after_help(&after_help);
+    
+    let variable_cmd = cmd!(--variable "Manage GitHub Actions variables of repo, environment or org")
+        .subcommand_required(true)
+        .subcommands([
+            cmd!(--set "Set variable from file, stdin or prompt")
+                .args([
+                    &actions_name_arg,
+                    &actions_repo_arg,
+                    &value_file_arg,
+                    &environment_arg,
+                    &actions_org_arg,
+                    &org_visibility_arg,
+                    &selected_repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--import "Set variables from dotenv file")
+                .args([
+                    &env_file_arg,
+                    &actions_repo_arg,
+                    &environment_arg,
+                    &actions_org_arg,
+                    &org_visibility_arg,
+                    &selected_repo_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--list "List variables")
+                .args([
+                    &actions_repo_arg,
+                    &environment_arg,
+                    &actions_org_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+            cmd!(--delete "Delete variable")
+                .args([
+                    &actions_name_arg,
+                    &actions_repo_arg,
+                    &environment_arg,
+                    &actions_org_arg,
+                    &token_arg,
+                    &cli_only_arg,
+                ]),
+        ])
+        .after_help(&after_help);
+    
     let help_full_cmd = cmd!(--"help-full" "Print help fully, describing every command")
         .disable_help_flag(true);
 
@@ -1451,6 +6452,14 @@ async fn async_main() {
         publish_cmd,
         clone_cmd,
         fork_cmd,
+        import_cmd,
+        protect_cmd,
+        repo_cmd,
+        collab_cmd,
+        invites_cmd,
+        settings_cmd,
+        secret_cmd,
+        variable_cmd,
         help_full_cmd,
     ];
 
@@ -1467,12 +6476,39 @@ async fn async_main() {
                 &submatches.get_one::<String>("name").map(|v| v.as_str()),
                 &submatches.get_one::<String>("description").map(|v| v.as_str()),
                 &submatches.get_flag("public"),
+                &submatches.get_one::<String>("gitignore").map(|v| v.as_str()),
+                &submatches.get_one::<String>("license").map(|v| v.as_str()),
+                &submatches.get_one::<String>("template").map(|v| v.as_str()),
+                &submatches.get_many::<String>("template_var").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                &submatches.get_one::<String>("from_template").map(|v| v.as_str()),
+                &submatches.get_flag("include_all_branches"),
+                &submatches.get_one::<String>("org").map(|v| v.as_str()),
+                &submatches.get_many::<String>("team").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                &submatches.get_many::<String>("invite").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                &get_repo_create_options_raw(submatches),
+                &submatches.get_flag("protect"),
                 &submatches.get_one::<String>("token").map(|v| v.as_str()),
                 &submatches.get_flag("cli_only"),
             ).await,
             "publish" => run_publish_cmd(
+                &submatches.get_one::<String>("name").map(|v| v.as_str()),
                 &submatches.get_one::<String>("description").map(|v| v.as_str()),
+                &submatches.get_one::<String>("topics").map(|v| v.as_str()),
                 &submatches.get_flag("public"),
+                &submatches.get_one::<String>("remote").map(|v| v.as_str()),
+                &submatches.get_flag("all_branches"),
+                &submatches.get_flag("tags"),
+                &submatches.get_flag("interactive"),
+                &submatches.get_flag("allow_secrets"),
+                &submatches.get_many::<String>("strip_path").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                &submatches.get_one::<String>("strip_blobs_bigger_than").map(|v| v.as_str()),
+                &submatches.get_one::<String>("mailmap").map(|v| v.as_str()),
+                &submatches.get_one::<String>("gitignore").map(|v| v.as_str()),
+                &submatches.get_one::<String>("license").map(|v| v.as_str()),
+                &submatches.get_one::<String>("org").map(|v| v.as_str()),
+                &submatches.get_many::<String>("team").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                &get_repo_create_options_raw(submatches),
+                &submatches.get_flag("protect"),
                 &submatches.get_one::<String>("token").map(|v| v.as_str()),
                 &submatches.get_flag("cli_only"),
             ).await,
@@ -1485,9 +6521,200 @@ async fn async_main() {
                 &submatches.get_one::<String>("external").map(|v| v.as_str()),
                 &submatches.get_one::<String>("name").map(|v| v.as_str()),
                 &submatches.get_flag("public"),
+                &submatches.get_flag("with_labels"),
+                &submatches.get_flag("with_milestones"),
+                &submatches.get_flag("with_releases"),
+                &submatches.get_flag("with_wiki"),
+                &submatches.get_flag("recurse_submodules"),
+                &submatches.get_many::<String>("strip_path").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                &submatches.get_one::<String>("strip_blobs_bigger_than").map(|v| v.as_str()),
+                &submatches.get_one::<String>("mailmap").map(|v| v.as_str()),
+                &submatches.get_one::<String>("org").map(|v| v.as_str()),
+                &submatches.get_many::<String>("team").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                &submatches.get_many::<String>("invite").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                &get_repo_create_options_raw(submatches),
+                &submatches.get_one::<String>("token").map(|v| v.as_str()),
+                &submatches.get_flag("cli_only"),
+            ).await,
+            "import" => run_import_cmd(
+                &submatches.get_one::<String>("source").map(|v| v.as_str()),
+                &submatches.get_one::<String>("name").map(|v| v.as_str()),
+                &submatches.get_one::<String>("description").map(|v| v.as_str()),
+                &submatches.get_flag("public"),
+                &submatches.get_one::<String>("token").map(|v| v.as_str()),
+                &submatches.get_flag("cli_only"),
+            ).await,
+            "protect" => run_protect_cmd(
+                &submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                &submatches.get_one::<String>("branch").map(|v| v.as_str()),
                 &submatches.get_one::<String>("token").map(|v| v.as_str()),
                 &submatches.get_flag("cli_only"),
             ).await,
+            "repo" => match submatches.subcommand() {
+                Some(("edit", repo_submatches)) => run_repo_edit_cmd(
+                    &repo_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("description").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("topics").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("default_branch").map(|v| v.as_str()),
+                    &RepoCreateOptionsRaw {
+                        homepage: repo_submatches.get_one::<String>("homepage").cloned(),
+                        has_issues: repo_submatches.get_one::<bool>("issues").copied(),
+                        has_projects: repo_submatches.get_one::<bool>("projects").copied(),
+                        has_wiki: repo_submatches.get_one::<bool>("wiki").copied(),
+                        has_discussions: repo_submatches.get_one::<bool>("discussions").copied(),
+                        is_template: repo_submatches.get_one::<bool>("is_template").copied(),
+                        merge_methods: repo_submatches.get_one::<String>("merge_methods").cloned(),
+                        delete_branch_on_merge: repo_submatches.get_one::<bool>("delete_branch_on_merge").copied(),
+                        allow_auto_merge: repo_submatches.get_one::<bool>("auto_merge").copied(),
+                        secret_scanning: repo_submatches.get_one::<bool>("secret_scanning").copied(),
+                        push_protection: repo_submatches.get_one::<bool>("push_protection").copied(),
+                        ..Default::default()
+                    },
+                    &repo_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("cli_only"),
+                ).await,
+                Some(("rename", repo_submatches)) => run_repo_rename_cmd(
+                    &repo_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("name").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("cli_only"),
+                ).await,
+                Some((archive_subcmd @ ("archive" | "unarchive"), repo_submatches)) => run_repo_archive_cmd(
+                    &repo_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                    &(archive_subcmd == "archive"),
+                    &repo_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("cli_only"),
+                ).await,
+                Some(("visibility", repo_submatches)) => run_repo_visibility_cmd(
+                    repo_submatches.get_one::<String>("visibility").expect("required"),
+                    &repo_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("cli_only"),
+                ).await,
+                Some(("delete", repo_submatches)) => run_repo_delete_cmd(
+                    &repo_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("yes"),
+                    &repo_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("cli_only"),
+                ).await,
+                Some(("transfer", repo_submatches)) => run_repo_transfer_cmd(
+                    repo_submatches.get_one::<String>("repo").expect("required"),
+                    repo_submatches.get_one::<String>("new_owner").expect("required"),
+                    &repo_submatches.get_one::<String>("new_name").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("clones_dir").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("mirror_fallback"),
+                    &repo_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("cli_only"),
+                ).await,
+                Some(("bulk", repo_submatches)) => run_repo_bulk_cmd(
+                    repo_submatches.get_one::<String>("action").expect("required"),
+                    &repo_submatches.get_one::<String>("new_topic").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("owner").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("name_regex").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<String>("pushed_before").map(|v| v.as_str()),
+                    &repo_submatches.get_one::<bool>("fork").copied(),
+                    &repo_submatches.get_one::<String>("language").map(|v| v.as_str()),
+                    &repo_submatches.get_many::<String>("topic").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                    repo_submatches.get_one::<usize>("concurrency").expect("has default"),
+                    &repo_submatches.get_flag("dry_run"),
+                    &repo_submatches.get_flag("yes"),
+                    &repo_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &repo_submatches.get_flag("cli_only"),
+                ).await,
+                _ => Ok(()),
+            },
+            "collab" => match submatches.subcommand() {
+                Some(("add", collab_submatches)) => run_collab_add_cmd(
+                    collab_submatches.get_one::<String>("user").expect("required"),
+                    &collab_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                    &collab_submatches.get_one::<String>("permission").map(|v| v.as_str()),
+                    &collab_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &collab_submatches.get_flag("cli_only"),
+                ).await,
+                Some(("remove", collab_submatches)) => run_collab_remove_cmd(
+                    collab_submatches.get_one::<String>("user").expect("required"),
+                    &collab_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                    &collab_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &collab_submatches.get_flag("cli_only"),
+                ).await,
+                Some(("list", collab_submatches)) => run_collab_list_cmd(
+                    &collab_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                    &collab_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &collab_submatches.get_flag("cli_only"),
+                ).await,
+                _ => Ok(()),
+            },
+            "invites" => match submatches.subcommand() {
+                Some(("list", invites_submatches)) => run_invites_list_cmd(
+                    &invites_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &invites_submatches.get_flag("cli_only"),
+                ).await,
+                Some((invites_subcmd, invites_submatches)) => run_invites_respond_cmd(
+                    invites_submatches.get_one::<String>("invitation").expect("required"),
+                    &(invites_subcmd == "accept"),
+                    &invites_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &invites_submatches.get_flag("cli_only"),
+                ).await,
+                None => Ok(()),
+            },
+            "settings" => match submatches.subcommand() {
+                Some((settings_subcmd, settings_submatches)) => run_settings_cmd(
+                    settings_submatches.get_one::<String>("file").expect("required"),
+                    &(settings_subcmd == "apply"),
+                    &settings_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                    &settings_submatches.get_flag("cli_only"),
+                ).await,
+                None => Ok(()),
+            },
+            "secret" | "variable" => {
+                let kind = match subcmd {
+                    "secret" => "secrets",
+                    _ => "variables",
+                };
+                match submatches.subcommand() {
+                    Some(("set", actions_submatches)) => run_actions_set_cmd(
+                        kind,
+                        actions_submatches.get_one::<String>("name").expect("required"),
+                        &actions_submatches.get_one::<String>("file").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("environment").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("org").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("visibility").map(|v| v.as_str()),
+                        &actions_submatches.get_many::<String>("selected_repo").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                        &actions_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                        &actions_submatches.get_flag("cli_only"),
+                    ).await,
+                    Some(("import", actions_submatches)) => run_actions_import_cmd(
+                        kind,
+                        actions_submatches.get_one::<String>("env_file").expect("required"),
+                        &actions_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("environment").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("org").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("visibility").map(|v| v.as_str()),
+                        &actions_submatches.get_many::<String>("selected_repo").map(|v| v.map(|p| p.as_str()).collect()).unwrap_or_default(),
+                        &actions_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                        &actions_submatches.get_flag("cli_only"),
+                    ).await,
+                    Some(("list", actions_submatches)) => run_actions_list_cmd(
+                        kind,
+                        &actions_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("environment").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("org").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                        &actions_submatches.get_flag("cli_only"),
+                    ).await,
+                    Some(("delete", actions_submatches)) => run_actions_delete_cmd(
+                        kind,
+                        actions_submatches.get_one::<String>("name").expect("required"),
+                        &actions_submatches.get_one::<String>("repo").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("environment").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("org").map(|v| v.as_str()),
+                        &actions_submatches.get_one::<String>("token").map(|v| v.as_str()),
+                        &actions_submatches.get_flag("cli_only"),
+                    ).await,
+                    _ => Ok(()),
+                }
+            },
             "help-full" => {
                 let st_str = cmd_help_expanded_subcommands(&root_cmd, subcommands);
                 aprintln!("{}", st_str.ansi());
@@ -1520,3 +6747,338 @@ pub fn main() {
         .unwrap()
         .block_on(async { async_main().await });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_import_source_urls() {
+        let cases = [
+            ("https://gitlab.com/group/project.git", "project"),
+            ("ssh://git@host.example:2222/team/tool/", "tool"),
+            ("git@github.com:owner/repo.git", "repo"),
+            ("host.example:team/service.git", "service"),
+            ("host.example:service", "service"),
+            ("file:///srv/git/mirror.git", "mirror"),
+        ];
+        for (source, name) in cases {
+            assert_eq!(resolve_import_source(source), Some((source.to_string(), name.to_string())), "{source}");
+        }
+    }
+
+    #[test]
+    fn resolve_import_source_local_paths() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let repo_path = repo_dir.path().join("local-project");
+        git2::Repository::init(&repo_path).expect("can init repo");
+        let repo_path_canonical = std::fs::canonicalize(&repo_path).expect("exists").to_str().expect("utf-8").to_string();
+        let (source_url, name) = resolve_import_source(repo_path.to_str().expect("utf-8")).expect("is a repo");
+        assert_eq!((source_url.as_str(), name.as_str()), (repo_path_canonical.as_str(), "local-project"));
+        // paths are never scp-like, even with a colon after a slash
+        assert_eq!(resolve_import_source(&format!("{dir}/no:repo", dir = repo_dir.path().display())), None);
+        assert_eq!(resolve_import_source("./missing:repo"), None);
+        assert_eq!(resolve_import_source("C:\\repos\\project"), None);
+        assert_eq!(resolve_import_source("  "), None);
+    }
+
+    #[test]
+    fn parse_size_units() {
+        assert_eq!(parse_size("500"), Some(500));
+        assert_eq!(parse_size("100K"), Some(100 * 1024));
+        assert_eq!(parse_size(" 10mb "), Some(10 * 1024 * 1024));
+        assert_eq!(parse_size("1G"), Some(1024 * 1024 * 1024));
+        assert_eq!(parse_size("2 M"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_size(""), None);
+        assert_eq!(parse_size("1.5M"), None);
+        assert_eq!(parse_size("tenK"), None);
+        assert_eq!(parse_size("-1"), None);
+    }
+
+    #[test]
+    fn format_size_units() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(100 * 1024 * 1024), "100.0 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn git2_commit_paths_commits_selected_paths_only() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let repo = git2::Repository::init(repo_dir.path()).expect("can init repo");
+        let signature = test_git::signature();
+        std::fs::create_dir_all(repo_dir.path().join("src/bin")).expect("can create dir");
+        std::fs::write(repo_dir.path().join("src/bin/main.rs"), "fn main() {}\n").expect("can write file");
+        std::fs::write(repo_dir.path().join("staged.txt"), "work in progress\n").expect("can write file");
+        std::fs::write(repo_dir.path().join("other.txt"), "not selected\n").expect("can write file");
+        let mut index = repo.index().expect("has index");
+        index.add_path(std::path::Path::new("staged.txt")).expect("can stage");
+        index.write().expect("can write index");
+
+        // first commit has no HEAD to build on
+        let commit = git2_commit_paths(&repo, &signature, &["src/bin/main.rs".to_string()], "init").expect("can commit");
+        let init_commit_id = commit.id();
+        assert_eq!(commit.parent_count(), 0);
+        let tree = commit.tree().expect("has tree");
+        assert!(tree.get_path(std::path::Path::new("src/bin/main.rs")).is_ok());
+        assert!(tree.get_name("staged.txt").is_none());
+        assert!(tree.get_name("other.txt").is_none());
+
+        std::fs::write(repo_dir.path().join("src/bin/main.rs"), "fn main() { println!(); }\n").expect("can write file");
+        std::fs::write(repo_dir.path().join("src/lib.rs"), "\n").expect("can write file");
+        let commit = git2_commit_paths(&repo, &signature, &["src/lib.rs".to_string()], "lib").expect("can commit");
+        assert_eq!(commit.parent_id(0).expect("has parent"), init_commit_id);
+        let tree = commit.tree().expect("has tree");
+        let main_blob = repo.find_blob(tree.get_path(std::path::Path::new("src/bin/main.rs")).expect("kept").id()).expect("is blob");
+        assert_eq!(main_blob.content(), b"fn main() {}\n");
+        assert!(tree.get_path(std::path::Path::new("src/lib.rs")).is_ok());
+        assert!(tree.get_name("staged.txt").is_none());
+
+        // unrelated staged file is still staged
+        let status = repo.status_file(std::path::Path::new("staged.txt")).expect("has status");
+        assert_eq!(status, git2::Status::INDEX_NEW);
+    }
+
+    #[test]
+    fn git2_commit_paths_scaffold_files_on_history() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let repo = test_git::repo_with_commit(repo_dir.path());
+        std::fs::write(repo_dir.path().join("staged.txt"), "work in progress\n").expect("can write file");
+        let mut index = repo.index().expect("has index");
+        index.add_path(std::path::Path::new("staged.txt")).expect("can stage");
+        index.write().expect("can write index");
+        std::fs::write(repo_dir.path().join(".gitignore"), "/target\n").expect("can write file");
+
+        let signature = git2::Signature::now("test", "test@example.com").expect("valid signature");
+        let commit = git2_commit_paths(&repo, &signature, &[".gitignore".to_string()], "Add .gitignore")
+            .expect("can commit");
+        let tree = commit.tree().expect("has tree");
+        assert!(tree.get_name(".gitignore").is_some());
+        assert!(tree.get_name("README.md").is_some());
+        assert!(tree.get_name("staged.txt").is_none());
+        assert_eq!(repo.head().expect("has head").target(), Some(commit.id()));
+
+        let statuses = repo.statuses(None).expect("can read status");
+        let changed: Vec<(String, git2::Status)> = statuses.iter()
+            .map(|s| (s.path().expect("utf-8").to_string(), s.status()))
+            .collect();
+        assert_eq!(changed, vec![("staged.txt".to_string(), git2::Status::INDEX_NEW)]);
+    }
+
+    #[test]
+    fn git2_history_size_counts_reachable_objects() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let repo = test_git::repo_with_commit(repo_dir.path());
+        let branch_ref_name = repo.head().expect("has head").name().expect("utf-8").to_string();
+        let odb = repo.odb().expect("has odb");
+        let header_size = |id: git2::Oid| odb.read_header(id).expect("object exists").0;
+        let first_commit = repo.head().expect("has head").peel_to_commit().expect("is commit");
+
+        // second commit adds a big file in a subdirectory & keeps README.md blob
+        std::fs::create_dir(repo_dir.path().join("assets")).expect("can create dir");
+        std::fs::write(repo_dir.path().join("assets/big.bin"), vec![7u8; 5000]).expect("can write file");
+        let mut index = repo.index().expect("has index");
+        index.add_path(std::path::Path::new("assets/big.bin")).expect("can stage");
+        let tree = repo.find_tree(index.write_tree().expect("can write tree")).expect("tree exists");
+        let signature = git2::Signature::now("test", "test@example.com").expect("valid signature");
+        let second_commit_id = repo.commit(Some("HEAD"), &signature, &signature, "assets", &tree, &[&first_commit]).expect("can commit");
+
+        let assets_tree_id = tree.get_name("assets").expect("has assets").id();
+        let big_blob_id = repo.find_tree(assets_tree_id).expect("is tree").get_name("big.bin").expect("has file").id();
+        let expected_size = header_size(first_commit.id()) + header_size(first_commit.tree_id())
+            + header_size(first_commit.tree().expect("has tree").get_name("README.md").expect("has readme").id())
+            + header_size(second_commit_id) + header_size(tree.id()) + header_size(assets_tree_id) + 5000;
+        assert_eq!(header_size(big_blob_id), 5000);
+
+        let (large_blobs, objects_size) = git2_history_size(&repo, &[branch_ref_name.as_str()], 1000).expect("can walk");
+        assert_eq!(large_blobs, vec![("assets/big.bin".to_string(), 5000)]);
+        assert_eq!(objects_size, expected_size);
+        let (large_blobs, _) = git2_history_size(&repo, &[branch_ref_name.as_str()], 10_000).expect("can walk");
+        assert!(large_blobs.is_empty());
+    }
+
+    #[test]
+    fn git2_push_refspecs_keeps_matching_remote() {
+        let remote_dir = tempfile::tempdir().expect("can create temp dir");
+        git2::Repository::init_bare(remote_dir.path()).expect("can init bare repo");
+        let remote_url = remote_dir.path().to_str().expect("utf-8").to_string();
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let repo = test_git::repo_with_commit(repo_dir.path());
+        let head_id = repo.head().expect("has head").target().expect("direct ref");
+        let branch_name = git2_default_branch_name(&repo).expect("has branch");
+        repo.remote("origin", &remote_url).expect("can add remote");
+        repo.reference("refs/remotes/origin/feature", head_id, false, "test").expect("can create ref");
+        let ssh_private_key = ssh_key::PrivateKey::random(&mut rand_core::OsRng, ssh_key::Algorithm::Ed25519).expect("can generate key");
+        let refspecs = vec![format!("refs/heads/{branch_name}")];
+
+        git2_push_refspecs(&repo, "origin", &remote_url, &refspecs, "", "", &ssh_private_key).expect("can push");
+        assert!(repo.find_reference("refs/remotes/origin/feature").is_ok());
+        let remote_repo = git2::Repository::open_bare(remote_dir.path()).expect("can open");
+        assert_eq!(remote_repo.refname_to_id(&format!("refs/heads/{branch_name}")).expect("pushed"), head_id);
+
+        // other url replaces remote & its tracking branches
+        let other_remote_dir = tempfile::tempdir().expect("can create temp dir");
+        git2::Repository::init_bare(other_remote_dir.path()).expect("can init bare repo");
+        let other_remote_url = other_remote_dir.path().to_str().expect("utf-8").to_string();
+        git2_push_refspecs(&repo, "origin", &other_remote_url, &refspecs, "", "", &ssh_private_key).expect("can push");
+        assert!(repo.find_reference("refs/remotes/origin/feature").is_err());
+        assert_eq!(repo.find_remote("origin").expect("exists").url(), Some(other_remote_url.as_str()));
+    }
+
+    fn test_gh_repo(fields: serde_json::Value) -> octocrab::models::Repository {
+        let mut gh_repo = serde_json::json!({ "id": 1, "name": "service-api", "url": "https://api.github.com/repos/gh0st-work/service-api" });
+        gh_repo.as_object_mut().expect("is object").extend(fields.as_object().expect("is object").clone());
+        serde_json::from_value(gh_repo).expect("valid repo")
+    }
+
+    #[test]
+    fn bulk_repo_matches_filters() {
+        let gh_repo = test_gh_repo(serde_json::json!({
+            "pushed_at": "2020-01-01T00:00:00Z",
+            "fork": false,
+            "language": "Rust",
+            "topics": ["cli", "rust"],
+        }));
+        let date = |d: &str| chrono::DateTime::parse_from_rfc3339(d).expect("valid date").with_timezone(&chrono::Utc);
+        assert!(bulk_repo_matches(&gh_repo, &BulkRepoFilters::default()));
+
+        let name_filters = |r: &str| BulkRepoFilters { name_regex: Some(regex::Regex::new(r).expect("valid regex")), ..Default::default() };
+        assert!(bulk_repo_matches(&gh_repo, &name_filters("^service-")));
+        assert!(!bulk_repo_matches(&gh_repo, &name_filters("^web-")));
+
+        let pushed_filters = |d: &str| BulkRepoFilters { pushed_before: Some(date(d)), ..Default::default() };
+        assert!(bulk_repo_matches(&gh_repo, &pushed_filters("2021-01-01T00:00:00Z")));
+        assert!(!bulk_repo_matches(&gh_repo, &pushed_filters("2019-01-01T00:00:00Z")));
+        // never pushed counts as stale
+        let never_pushed_repo = test_gh_repo(serde_json::json!({}));
+        assert!(bulk_repo_matches(&never_pushed_repo, &pushed_filters("2019-01-01T00:00:00Z")));
+
+        assert!(bulk_repo_matches(&gh_repo, &BulkRepoFilters { fork: Some(false), ..Default::default() }));
+        assert!(!bulk_repo_matches(&gh_repo, &BulkRepoFilters { fork: Some(true), ..Default::default() }));
+        assert!(bulk_repo_matches(&never_pushed_repo, &BulkRepoFilters { fork: Some(false), ..Default::default() }));
+
+        assert!(bulk_repo_matches(&gh_repo, &BulkRepoFilters { language: Some("rust".to_string()), ..Default::default() }));
+        assert!(!bulk_repo_matches(&gh_repo, &BulkRepoFilters { language: Some("Go".to_string()), ..Default::default() }));
+        assert!(!bulk_repo_matches(&never_pushed_repo, &BulkRepoFilters { language: Some("rust".to_string()), ..Default::default() }));
+
+        let topics_filters = |t: &[&str]| BulkRepoFilters { topics: t.iter().map(|t| t.to_string()).collect(), ..Default::default() };
+        assert!(bulk_repo_matches(&gh_repo, &topics_filters(&["RUST", "cli"])));
+        assert!(!bulk_repo_matches(&gh_repo, &topics_filters(&["rust", "web"])));
+    }
+
+    #[test]
+    fn bulk_action_applies_skips_repos_in_target_state() {
+        let public_repo = test_gh_repo(serde_json::json!({ "private": false, "archived": false, "topics": ["rust"] }));
+        let private_repo = test_gh_repo(serde_json::json!({ "private": true, "archived": false }));
+        let archived_repo = test_gh_repo(serde_json::json!({ "private": true, "archived": true }));
+
+        assert!(bulk_action_applies(&public_repo, "archive", None));
+        assert!(!bulk_action_applies(&archived_repo, "archive", None));
+        assert!(bulk_action_applies(&archived_repo, "unarchive", None));
+        assert!(!bulk_action_applies(&public_repo, "unarchive", None));
+
+        assert!(bulk_action_applies(&public_repo, "make-private", None));
+        assert!(!bulk_action_applies(&private_repo, "make-private", None));
+        assert!(bulk_action_applies(&private_repo, "make-public", None));
+        assert!(!bulk_action_applies(&public_repo, "make-public", None));
+        // archived repos are read-only
+        assert!(!bulk_action_applies(&archived_repo, "make-public", None));
+
+        assert!(bulk_action_applies(&public_repo, "add-topic", Some("cli")));
+        assert!(!bulk_action_applies(&public_repo, "add-topic", Some("rust")));
+        assert!(!bulk_action_applies(&archived_repo, "add-topic", Some("cli")));
+
+        assert!(bulk_action_applies(&archived_repo, "delete", None));
+        assert!(!bulk_action_applies(&public_repo, "rename", None));
+    }
+
+    #[test]
+    fn format_table_pads_columns() {
+        let rows = vec![
+            vec!["service-api".to_string(), "Rust".to_string(), "".to_string()],
+            vec!["web".to_string(), "TypeScript".to_string(), "archived".to_string()],
+        ];
+        assert_eq!(format_table(&["NAME", "LANGUAGE", "STATE"], &rows), [
+            "NAME         LANGUAGE    STATE",
+            "service-api  Rust",
+            "web          TypeScript  archived",
+        ]);
+        assert_eq!(format_table(&["NAME"], &[]), ["NAME"]);
+    }
+
+    #[test]
+    fn git2_remotes_retarget_rewrites_matching_remotes_only() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let repo = git2::Repository::init(repo_dir.path()).expect("can init repo");
+        let remotes = [
+            ("origin", "https://github.com/Old-Owner/Proj.git"),
+            ("ssh", "ssh://git@github.com/old-owner/proj"),
+            ("scp", "git@github.com:old-owner/proj.git"),
+            ("extra", "https://github.com/old-owner/proj-extra.git"),
+            ("fork", "https://github.com/someone/proj.git"),
+            ("gitlab", "https://gitlab.com/old-owner/proj.git"),
+        ];
+        for (remote_name, remote_url) in remotes {
+            repo.remote(remote_name, remote_url).expect("can add remote");
+        }
+        repo.remote_set_pushurl("fork", Some("git@github.com:old-owner/proj.git")).expect("can set pushurl");
+
+        let mut changed = git2_remotes_retarget(&repo, "old-owner", "PROJ", "new-org", "renamed").expect("can retarget");
+        changed.sort();
+        assert_eq!(changed, ["fork", "origin", "scp", "ssh"]);
+        let remote_urls = |remote_name: &str| {
+            let remote = repo.find_remote(remote_name).expect("remote exists");
+            (remote.url().map(|u| u.to_string()), remote.pushurl().map(|u| u.to_string()))
+        };
+        assert_eq!(remote_urls("origin"), (Some("https://github.com/new-org/renamed.git".to_string()), None));
+        assert_eq!(remote_urls("ssh"), (Some("ssh://git@github.com/new-org/renamed".to_string()), None));
+        assert_eq!(remote_urls("scp"), (Some("git@github.com:new-org/renamed.git".to_string()), None));
+        // only pushurl of fork points to the moved repo
+        assert_eq!(remote_urls("fork"), (
+            Some("https://github.com/someone/proj.git".to_string()),
+            Some("git@github.com:new-org/renamed.git".to_string()),
+        ));
+        assert_eq!(remote_urls("extra").0.as_deref(), Some("https://github.com/old-owner/proj-extra.git"));
+        assert_eq!(remote_urls("gitlab").0.as_deref(), Some("https://gitlab.com/old-owner/proj.git"));
+    }
+
+    #[test]
+    fn git_repos_find_sync_skips_nested_and_hidden() {
+        let clones_dir = tempfile::tempdir().expect("can create temp dir");
+        let clones_path = clones_dir.path();
+        for repo_rel in ["a", "group/b", "group/b/vendor/nested", ".cache/c", "node_modules/d", "one/two/three/four/too-deep"] {
+            git2::Repository::init(clones_path.join(repo_rel)).expect("can init repo");
+        }
+        git2::Repository::init_bare(clones_path.join("group/mirror.git")).expect("can init bare repo");
+        std::fs::create_dir_all(clones_path.join("empty/dir")).expect("can create dir");
+
+        let mut repo_paths = vec![];
+        git_repos_find_sync(clones_path, 4, &mut repo_paths);
+        repo_paths.sort();
+        assert_eq!(repo_paths, [clones_path.join("a"), clones_path.join("group/b"), clones_path.join("group/mirror.git")]);
+    }
+
+    #[test]
+    fn github_git_urls_match_any_transport() {
+        let https_url = "https://github.com/gh0st-work/gh.rs.git";
+        assert!(github_git_urls_match("git@github.com:gh0st-work/gh.rs.git", https_url));
+        assert!(github_git_urls_match("ssh://git@github.com/Gh0st-Work/GH.rs", https_url));
+        assert!(github_git_urls_match(https_url, https_url));
+        assert!(!github_git_urls_match("git@github.com:gh0st-work/other.git", https_url));
+        assert!(!github_git_urls_match("https://gitlab.com/gh0st-work/gh.rs.git", https_url));
+        assert!(github_git_urls_match("/tmp/remote.git", "/tmp/remote.git"));
+    }
+
+    #[tokio::test]
+    async fn get_publish_remote_name_reuses_ssh_origin() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let repo = test_git::repo_with_commit(repo_dir.path());
+        repo.remote("origin", "git@github.com:gh0st-work/gh.rs.git").expect("can add remote");
+        let remote_name = get_publish_remote_name(&repo, &None, "https://github.com/gh0st-work/gh.rs.git", &true).await;
+        assert_eq!(remote_name.expect("origin is reused"), "origin");
+        let remote_name = get_publish_remote_name(&repo, &None, "https://github.com/gh0st-work/other.git", &true).await;
+        assert!(remote_name.is_err());
+    }
+}
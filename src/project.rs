@@ -0,0 +1,237 @@
+use std::path::Path;
+
+// Project manifests (Cargo.toml, package.json, pyproject.toml, go.mod) found in a directory
+
+const topics_max: usize = 20;
+const topic_len_max: usize = 50;
+
+#[derive(Debug, Clone, Default)]
+pub struct ProjectMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub keywords: Vec<String>,
+    pub language: Option<String>,
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    let text = std::fs::read_to_string(path).ok()?;
+    text.parse::<toml::Table>().ok()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let text = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.map(|v| v.trim()).filter(|v| !v.is_empty()).map(|v| v.to_string())
+}
+
+fn strings_toml(value: Option<&toml::Value>) -> Vec<String> {
+    value.and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).map(|v| v.to_string()).collect())
+        .unwrap_or_default()
+}
+
+fn strings_json(value: Option<&serde_json::Value>) -> Vec<String> {
+    value.and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).map(|v| v.to_string()).collect())
+        .unwrap_or_default()
+}
+
+fn metadata_cargo(dir: &Path) -> Option<ProjectMetadata> {
+    let cargo_toml = read_toml(&dir.join("Cargo.toml"))?;
+    let package = cargo_toml.get("package")?;
+    let mut keywords = strings_toml(package.get("keywords"));
+    // "development-tools::cargo-plugins" -> "development-tools", "cargo-plugins"
+    for category in strings_toml(package.get("categories")) {
+        keywords.extend(category.split("::").map(|c| c.to_string()));
+    }
+    Some(ProjectMetadata {
+        name: non_empty(package.get("name").and_then(|v| v.as_str())),
+        description: non_empty(package.get("description").and_then(|v| v.as_str())),
+        homepage: non_empty(package.get("homepage").and_then(|v| v.as_str())),
+        keywords,
+        language: Some("rust".to_string()),
+    })
+}
+
+fn metadata_npm(dir: &Path) -> Option<ProjectMetadata> {
+    let package_json = read_json(&dir.join("package.json"))?;
+    // "@scope/name" packages
+    let name = package_json.get("name").and_then(|v| v.as_str())
+        .map(|n| n.rsplit('/').next().unwrap_or(n));
+    let language = match dir.join("tsconfig.json").is_file() {
+        true => "typescript",
+        false => "javascript",
+    };
+    Some(ProjectMetadata {
+        name: non_empty(name),
+        description: non_empty(package_json.get("description").and_then(|v| v.as_str())),
+        homepage: non_empty(package_json.get("homepage").and_then(|v| v.as_str())),
+        keywords: strings_json(package_json.get("keywords")),
+        language: Some(language.to_string()),
+    })
+}
+
+fn metadata_python(dir: &Path) -> Option<ProjectMetadata> {
+    let pyproject_toml = read_toml(&dir.join("pyproject.toml"))?;
+    // PEP 621 [project] table, older poetry projects keep it in [tool.poetry]
+    let project = pyproject_toml.get("project")
+        .or_else(|| pyproject_toml.get("tool").and_then(|t| t.get("poetry")))?;
+    let homepage = project.get("homepage")
+        .or_else(|| project.get("urls").and_then(|u| u.get("Homepage").or_else(|| u.get("homepage"))))
+        .and_then(|v| v.as_str());
+    Some(ProjectMetadata {
+        name: non_empty(project.get("name").and_then(|v| v.as_str())),
+        description: non_empty(project.get("description").and_then(|v| v.as_str())),
+        homepage: non_empty(homepage),
+        keywords: strings_toml(project.get("keywords")),
+        language: Some("python".to_string()),
+    })
+}
+
+fn metadata_go(dir: &Path) -> Option<ProjectMetadata> {
+    let go_mod = std::fs::read_to_string(dir.join("go.mod")).ok()?;
+    // "module github.com/owner/name"
+    let module_path = go_mod.lines()
+        .find_map(|l| l.trim().strip_prefix("module "))
+        .map(|m| m.trim().trim_matches('"'));
+    Some(ProjectMetadata {
+        name: non_empty(module_path.map(|m| m.rsplit('/').next().unwrap_or(m))),
+        language: Some("go".to_string()),
+        ..Default::default()
+    })
+}
+
+pub fn metadata(dir: &Path) -> ProjectMetadata {
+    metadata_cargo(dir)
+        .or_else(|| metadata_npm(dir))
+        .or_else(|| metadata_python(dir))
+        .or_else(|| metadata_go(dir))
+        .unwrap_or_default()
+}
+
+// GitHub replaces every char outside of [A-Za-z0-9._-] with "-", do the same
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for ch in name.trim().chars() {
+        let ch = match ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' || ch == '-' {
+            true => ch,
+            false => '-',
+        };
+        if ch == '-' && slug.ends_with('-') { continue; }
+        slug.push(ch);
+    }
+    slug.trim_matches(|ch| ch == '-' || ch == '.').to_string()
+}
+
+// Topics are lowercase letters, numbers & hyphens, starting with a letter or number
+pub fn topic_slug(keyword: &str) -> Option<String> {
+    let mut topic = String::new();
+    for ch in keyword.trim().to_lowercase().chars() {
+        let ch = match ch.is_ascii_alphanumeric() {
+            true => ch,
+            false => '-',
+        };
+        if ch == '-' && (topic.is_empty() || topic.ends_with('-')) { continue; }
+        topic.push(ch);
+    }
+    let topic = topic.trim_end_matches('-').to_string();
+    match topic.is_empty() || topic.len() > topic_len_max {
+        true => None,
+        false => Some(topic),
+    }
+}
+
+pub fn suggested_topics(project_metadata: &ProjectMetadata) -> Vec<String> {
+    let mut topics: Vec<String> = vec![];
+    let candidates = project_metadata.language.iter().chain(project_metadata.keywords.iter());
+    for topic in candidates.filter_map(|k| topic_slug(k)) {
+        if topics.len() >= topics_max { break; }
+        if !topics.contains(&topic) {
+            topics.push(topic);
+        }
+    }
+    topics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_like_github() {
+        assert_eq!(slugify("my-project"), "my-project");
+        assert_eq!(slugify("  My Cool Project  "), "My-Cool-Project");
+        assert_eq!(slugify("foo___bar.rs"), "foo___bar.rs");
+        assert_eq!(slugify("a  /  b"), "a-b");
+        assert_eq!(slugify("--.hidden.--"), "hidden");
+        assert_eq!(slugify("Проект"), "");
+        assert_eq!(slugify("café v2"), "caf-v2");
+    }
+
+    #[test]
+    fn topic_slug_rules() {
+        assert_eq!(topic_slug("Command Line Utilities").as_deref(), Some("command-line-utilities"));
+        assert_eq!(topic_slug("--async--").as_deref(), Some("async"));
+        assert_eq!(topic_slug("c++").as_deref(), Some("c"));
+        assert_eq!(topic_slug("web_assembly").as_deref(), Some("web-assembly"));
+        assert_eq!(topic_slug("!!!"), None);
+        assert_eq!(topic_slug(&"a".repeat(topic_len_max)).map(|t| t.len()), Some(topic_len_max));
+        assert_eq!(topic_slug(&"a".repeat(topic_len_max + 1)), None);
+    }
+
+    #[test]
+    fn suggested_topics_language_first_deduplicated() {
+        let project_metadata = ProjectMetadata {
+            language: Some("rust".to_string()),
+            keywords: vec!["CLI".to_string(), "cli".to_string(), "Rust".to_string(), "???".to_string(), "git hub".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(suggested_topics(&project_metadata), vec!["rust", "cli", "git-hub"]);
+        let many_keywords = ProjectMetadata {
+            keywords: (0..30).map(|i| format!("topic{i}")).collect(),
+            ..Default::default()
+        };
+        assert_eq!(suggested_topics(&many_keywords).len(), topics_max);
+    }
+
+    #[test]
+    fn metadata_from_manifests() {
+        let cargo_dir = tempfile::tempdir().expect("can create temp dir");
+        std::fs::write(cargo_dir.path().join("Cargo.toml"), r#"
+[package]
+name = "tool"
+description = " Does things "
+keywords = ["cli"]
+categories = ["development-tools::cargo-plugins"]
+"#).expect("can write manifest");
+        let cargo_metadata = metadata(cargo_dir.path());
+        assert_eq!(cargo_metadata.name.as_deref(), Some("tool"));
+        assert_eq!(cargo_metadata.description.as_deref(), Some("Does things"));
+        assert_eq!(cargo_metadata.keywords, vec!["cli", "development-tools", "cargo-plugins"]);
+        assert_eq!(cargo_metadata.language.as_deref(), Some("rust"));
+
+        let npm_dir = tempfile::tempdir().expect("can create temp dir");
+        std::fs::write(npm_dir.path().join("package.json"), r#"{"name": "@scope/widget", "homepage": "https://example.com"}"#).expect("can write manifest");
+        std::fs::write(npm_dir.path().join("tsconfig.json"), "{}").expect("can write tsconfig");
+        let npm_metadata = metadata(npm_dir.path());
+        assert_eq!(npm_metadata.name.as_deref(), Some("widget"));
+        assert_eq!(npm_metadata.homepage.as_deref(), Some("https://example.com"));
+        assert_eq!(npm_metadata.language.as_deref(), Some("typescript"));
+
+        let poetry_dir = tempfile::tempdir().expect("can create temp dir");
+        std::fs::write(poetry_dir.path().join("pyproject.toml"), "[tool.poetry]\nname = \"lib\"\nkeywords = [\"data\"]\n").expect("can write manifest");
+        let poetry_metadata = metadata(poetry_dir.path());
+        assert_eq!((poetry_metadata.name.as_deref(), poetry_metadata.keywords), (Some("lib"), vec!["data".to_string()]));
+
+        let go_dir = tempfile::tempdir().expect("can create temp dir");
+        std::fs::write(go_dir.path().join("go.mod"), "module github.com/owner/service\n\ngo 1.21\n").expect("can write manifest");
+        assert_eq!(metadata(go_dir.path()).name.as_deref(), Some("service"));
+
+        let empty_dir = tempfile::tempdir().expect("can create temp dir");
+        assert!(metadata(empty_dir.path()).name.is_none());
+    }
+}
@@ -0,0 +1,224 @@
+// Default branch protection policy & its rulesets / classic branch protection API bodies
+
+// None is whatever branch is default, rulesets follow default branch renames
+pub fn ruleset_name(branch_name_opt: Option<&str>) -> String {
+    match branch_name_opt {
+        Some(branch_name) => format!("gh.rs {branch_name}"),
+        None => "gh.rs default branch".to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProtectionPolicy {
+    pub required_reviews: u32,
+    pub dismiss_stale_reviews: bool,
+    pub code_owner_reviews: bool,
+    pub status_checks: Vec<String>,
+    pub strict_status_checks: bool,
+    pub linear_history: bool,
+    pub allow_force_pushes: bool,
+    pub allow_deletions: bool,
+    pub enforce_admins: bool,
+}
+
+impl Default for ProtectionPolicy {
+    fn default() -> Self {
+        ProtectionPolicy {
+            required_reviews: 1,
+            dismiss_stale_reviews: true,
+            code_owner_reviews: false,
+            status_checks: vec![],
+            strict_status_checks: true,
+            linear_history: true,
+            allow_force_pushes: false,
+            allow_deletions: false,
+            enforce_admins: false,
+        }
+    }
+}
+
+impl ProtectionPolicy {
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = vec![];
+        if self.required_reviews > 0 {
+            lines.push(format!("pull requests with {count} approving reviews", count = self.required_reviews));
+        }
+        if !self.status_checks.is_empty() {
+            lines.push(format!("status checks: {checks}", checks = self.status_checks.join(", ")));
+        }
+        if self.linear_history {
+            lines.push("linear history".to_string());
+        }
+        if !self.allow_force_pushes {
+            lines.push("no force pushes".to_string());
+        }
+        if !self.allow_deletions {
+            lines.push("no deletion".to_string());
+        }
+        lines
+    }
+}
+
+// POST/PUT /repos/{owner}/{repo}/rulesets
+pub fn ruleset_body(policy: &ProtectionPolicy, branch_name_opt: Option<&str>) -> serde_json::Value {
+    let mut rules = vec![];
+    if !policy.allow_deletions {
+        rules.push(serde_json::json!({ "type": "deletion" }));
+    }
+    if !policy.allow_force_pushes {
+        rules.push(serde_json::json!({ "type": "non_fast_forward" }));
+    }
+    if policy.linear_history {
+        rules.push(serde_json::json!({ "type": "required_linear_history" }));
+    }
+    if policy.required_reviews > 0 || policy.code_owner_reviews {
+        rules.push(serde_json::json!({
+            "type": "pull_request",
+            "parameters": {
+                "required_approving_review_count": policy.required_reviews,
+                "dismiss_stale_reviews_on_push": policy.dismiss_stale_reviews,
+                "require_code_owner_review": policy.code_owner_reviews,
+                "require_last_push_approval": false,
+                "required_review_thread_resolution": false,
+            },
+        }));
+    }
+    if !policy.status_checks.is_empty() {
+        let checks: Vec<serde_json::Value> = policy.status_checks.iter()
+            .map(|c| serde_json::json!({ "context": c }))
+            .collect();
+        rules.push(serde_json::json!({
+            "type": "required_status_checks",
+            "parameters": {
+                "strict_required_status_checks_policy": policy.strict_status_checks,
+                "required_status_checks": checks,
+            },
+        }));
+    }
+    // repo admins can bypass unless enforced
+    let bypass_actors = match policy.enforce_admins {
+        true => serde_json::json!([]),
+        false => serde_json::json!([{ "actor_id": 5, "actor_type": "RepositoryRole", "bypass_mode": "always" }]),
+    };
+    let ref_name_include = match branch_name_opt {
+        Some(branch_name) => format!("refs/heads/{branch_name}"),
+        None => "~DEFAULT_BRANCH".to_string(),
+    };
+    serde_json::json!({
+        "name": ruleset_name(branch_name_opt),
+        "target": "branch",
+        "enforcement": "active",
+        "bypass_actors": bypass_actors,
+        "conditions": { "ref_name": { "include": [ref_name_include], "exclude": [] } },
+        "rules": rules,
+    })
+}
+
+// PUT /repos/{owner}/{repo}/branches/{branch}/protection, every top level field is required
+pub fn branch_protection_body(policy: &ProtectionPolicy) -> serde_json::Value {
+    let required_status_checks = match policy.status_checks.is_empty() {
+        true => serde_json::Value::Null,
+        false => serde_json::json!({
+            "strict": policy.strict_status_checks,
+            "contexts": policy.status_checks,
+        }),
+    };
+    let required_pull_request_reviews = match policy.required_reviews > 0 || policy.code_owner_reviews {
+        false => serde_json::Value::Null,
+        true => serde_json::json!({
+            "required_approving_review_count": policy.required_reviews,
+            "dismiss_stale_reviews": policy.dismiss_stale_reviews,
+            "require_code_owner_reviews": policy.code_owner_reviews,
+        }),
+    };
+    serde_json::json!({
+        "required_status_checks": required_status_checks,
+        "enforce_admins": policy.enforce_admins,
+        "required_pull_request_reviews": required_pull_request_reviews,
+        "restrictions": null,
+        "required_linear_history": policy.linear_history,
+        "allow_force_pushes": policy.allow_force_pushes,
+        "allow_deletions": policy.allow_deletions,
+    })
+}
+
+// Live value has every desired field, GitHub adds defaults for the rest
+fn json_contains(live: &serde_json::Value, desired: &serde_json::Value) -> bool {
+    match (live, desired) {
+        (serde_json::Value::Object(live_map), serde_json::Value::Object(desired_map)) => desired_map.iter()
+            .all(|(key, desired_value)| live_map.get(key).map(|v| json_contains(v, desired_value)).unwrap_or(false)),
+        (serde_json::Value::Array(live_items), serde_json::Value::Array(desired_items)) => live_items.len() == desired_items.len()
+            && desired_items.iter().all(|d| live_items.iter().any(|l| json_contains(l, d))),
+        (live, desired) => live == desired,
+    }
+}
+
+// Compares rules & conditions of live ruleset (GET /repos/{owner}/{repo}/rulesets/{id}) with ruleset_body
+pub fn ruleset_matches(live_ruleset: &serde_json::Value, desired_ruleset: &serde_json::Value) -> bool {
+    ["enforcement", "conditions", "rules", "bypass_actors"].iter()
+        .all(|key| json_contains(&live_ruleset[key], &desired_ruleset[key]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule_types(body: &serde_json::Value) -> Vec<String> {
+        body["rules"].as_array().expect("rules array").iter()
+            .map(|r| r["type"].as_str().expect("rule type").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn ruleset_body_default_policy() {
+        let body = ruleset_body(&ProtectionPolicy::default(), None);
+        assert_eq!(body["name"], "gh.rs default branch");
+        assert_eq!(body["conditions"]["ref_name"]["include"], serde_json::json!(["~DEFAULT_BRANCH"]));
+        assert_eq!(rule_types(&body), ["deletion", "non_fast_forward", "required_linear_history", "pull_request"]);
+        assert_eq!(body["rules"][3]["parameters"]["required_approving_review_count"], 1);
+        assert_eq!(body["bypass_actors"][0]["actor_type"], "RepositoryRole");
+    }
+
+    #[test]
+    fn ruleset_body_branch_checks_and_enforced_admins() {
+        let policy = ProtectionPolicy {
+            required_reviews: 0,
+            status_checks: vec!["ci".to_string(), "lint".to_string()],
+            linear_history: false,
+            allow_force_pushes: true,
+            allow_deletions: true,
+            enforce_admins: true,
+            ..ProtectionPolicy::default()
+        };
+        let body = ruleset_body(&policy, Some("release"));
+        assert_eq!(body["name"], "gh.rs release");
+        assert_eq!(body["conditions"]["ref_name"]["include"], serde_json::json!(["refs/heads/release"]));
+        assert_eq!(rule_types(&body), ["required_status_checks"]);
+        assert_eq!(
+            body["rules"][0]["parameters"]["required_status_checks"],
+            serde_json::json!([{ "context": "ci" }, { "context": "lint" }]),
+        );
+        assert_eq!(body["bypass_actors"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn ruleset_matches_ignores_live_defaults() {
+        let desired = ruleset_body(&ProtectionPolicy::default(), None);
+        let mut live = desired.clone();
+        live["id"] = serde_json::json!(42);
+        live["rules"][3]["parameters"]["automatic_copilot_code_review_enabled"] = serde_json::json!(false);
+        assert!(ruleset_matches(&live, &desired));
+
+        live["rules"].as_array_mut().expect("rules array").pop();
+        assert!(!ruleset_matches(&live, &desired));
+    }
+
+    #[test]
+    fn branch_protection_body_nulls_unused_sections() {
+        let policy = ProtectionPolicy { required_reviews: 0, ..ProtectionPolicy::default() };
+        let body = branch_protection_body(&policy);
+        assert!(body["required_status_checks"].is_null());
+        assert!(body["required_pull_request_reviews"].is_null());
+        assert_eq!(body["required_linear_history"], true);
+    }
+}
@@ -0,0 +1,269 @@
+use super::GhRsResult;
+use std::collections::HashMap;
+
+// History rewrite before push: strip paths & big blobs, map authors with mailmap
+
+#[derive(Debug, Clone, Default)]
+pub struct RewriteOptions {
+    pub strip_paths: Vec<String>,
+    pub strip_blobs_bigger_than: Option<usize>,
+    pub mailmap_path: Option<String>,
+}
+
+impl RewriteOptions {
+    pub fn is_empty(&self) -> bool {
+        self.strip_paths.is_empty() && self.strip_blobs_bigger_than.is_none() && self.mailmap_path.is_none()
+    }
+}
+
+struct Rewriter<'repo, 'options> {
+    repo: &'repo git2::Repository,
+    odb: git2::Odb<'repo>,
+    strip_paths: Vec<&'options str>,
+    strip_blobs_bigger_than: Option<usize>,
+    mailmap: Option<&'options git2::Mailmap>,
+    trees: HashMap<(git2::Oid, String), Option<git2::Oid>>,
+    commits: HashMap<git2::Oid, git2::Oid>,
+}
+
+impl<'repo, 'options> Rewriter<'repo, 'options> {
+    fn is_stripped_path(&self, path: &str) -> bool {
+        self.strip_paths.iter().any(|p| path == *p || path.starts_with(&format!("{p}/")))
+    }
+
+    // None when nothing is left in the tree, git doesn't keep empty trees
+    fn rewrite_tree(&mut self, tree_id: git2::Oid, tree_path: &str) -> GhRsResult<Option<git2::Oid>> {
+        let key = (tree_id, tree_path.to_string());
+        if let Some(new_tree_id) = self.trees.get(&key) {
+            return Ok(*new_tree_id);
+        }
+        let tree = self.repo.find_tree(tree_id)?;
+        let mut tree_builder = self.repo.treebuilder(None)?;
+        for entry in tree.iter() {
+            let name = match entry.name() {
+                Some(n) => n,
+                None => continue,
+            };
+            let path = format!("{tree_path}{name}");
+            if self.is_stripped_path(&path) { continue; }
+            let entry_id = match entry.kind() {
+                Some(git2::ObjectType::Tree) => match self.rewrite_tree(entry.id(), &format!("{path}/"))? {
+                    Some(id) => id,
+                    None => continue,
+                },
+                Some(git2::ObjectType::Blob) => {
+                    if let Some(size_max) = self.strip_blobs_bigger_than {
+                        let (size, _) = self.odb.read_header(entry.id())?;
+                        if size > size_max { continue; }
+                    }
+                    entry.id()
+                },
+                _ => entry.id(),
+            };
+            tree_builder.insert(name, entry_id, entry.filemode())?;
+        }
+        let new_tree_id = match tree_builder.len() {
+            0 => None,
+            _ => Some(tree_builder.write()?),
+        };
+        self.trees.insert(key, new_tree_id);
+        Ok(new_tree_id)
+    }
+
+    fn rewrite_signature(&self, signature: git2::Signature<'_>) -> GhRsResult<git2::Signature<'static>> {
+        match self.mailmap {
+            Some(mailmap) => Ok(mailmap.resolve_signature(&signature)?),
+            None => Ok(signature.to_owned()),
+        }
+    }
+
+    fn rewrite_commit(&mut self, commit_id: git2::Oid) -> GhRsResult<git2::Oid> {
+        let commit = self.repo.find_commit(commit_id)?;
+        let new_tree_id = match self.rewrite_tree(commit.tree_id(), "")? {
+            Some(id) => id,
+            None => self.repo.treebuilder(None)?.write()?,
+        };
+        let new_parent_ids: Vec<git2::Oid> = commit.parent_ids()
+            .map(|id| *self.commits.get(&id).unwrap_or(&id))
+            .collect();
+        let author = self.rewrite_signature(commit.author())?;
+        let committer = self.rewrite_signature(commit.committer())?;
+        let is_unchanged = new_tree_id == commit.tree_id()
+            && new_parent_ids.iter().eq(commit.parent_ids().collect::<Vec<git2::Oid>>().iter())
+            && author.name_bytes() == commit.author().name_bytes()
+            && author.email_bytes() == commit.author().email_bytes()
+            && committer.name_bytes() == commit.committer().name_bytes()
+            && committer.email_bytes() == commit.committer().email_bytes();
+        if is_unchanged {
+            return Ok(commit_id);
+        }
+        let new_tree = self.repo.find_tree(new_tree_id)?;
+        let mut new_parents = vec![];
+        for parent_id in &new_parent_ids {
+            new_parents.push(self.repo.find_commit(*parent_id)?);
+        }
+        let new_parents_refs: Vec<&git2::Commit> = new_parents.iter().collect();
+        let message = String::from_utf8_lossy(commit.message_raw_bytes()).to_string();
+        let new_commit_id = self.repo.commit(None, &author, &committer, &message, &new_tree, &new_parents_refs)?;
+        Ok(new_commit_id)
+    }
+}
+
+// Rewrites commits reachable from refs parents first, moves refs (old ones kept in refs/original/)
+// and returns changed commits as (old, new) in topological order
+pub fn rewrite_history(
+    repo: &git2::Repository,
+    ref_names: &[&str],
+    options: &RewriteOptions,
+    mailmap: Option<&git2::Mailmap>,
+) -> GhRsResult<Vec<(git2::Oid, git2::Oid)>> {
+    let mut rewriter = Rewriter {
+        repo,
+        odb: repo.odb()?,
+        strip_paths: options.strip_paths.iter()
+            .map(|p| p.trim_matches('/'))
+            .filter(|p| !p.is_empty())
+            .collect(),
+        strip_blobs_bigger_than: options.strip_blobs_bigger_than,
+        mailmap,
+        trees: HashMap::new(),
+        commits: HashMap::new(),
+    };
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    for ref_name in ref_names {
+        revwalk.push(repo.find_reference(ref_name)?.peel_to_commit()?.id())?;
+    }
+    let mut commit_map = vec![];
+    for commit_id in revwalk {
+        let commit_id = commit_id?;
+        let new_commit_id = rewriter.rewrite_commit(commit_id)?;
+        rewriter.commits.insert(commit_id, new_commit_id);
+        if new_commit_id != commit_id {
+            commit_map.push((commit_id, new_commit_id));
+        }
+    }
+
+    for ref_name in ref_names {
+        let reference = repo.find_reference(ref_name)?;
+        let commit_id = reference.peel_to_commit()?.id();
+        let new_commit_id = *rewriter.commits.get(&commit_id).unwrap_or(&commit_id);
+        if new_commit_id == commit_id { continue; }
+        let target_id = reference.target().expect("direct reference");
+        repo.reference(&format!("refs/original/{ref_name}"), target_id, true, "gh.rs rewrite backup")?;
+        let reflog_message = "gh.rs rewrite";
+        match repo.find_tag(target_id) {
+            // annotated tag, tag object has to point to the new commit
+            Ok(tag) => {
+                let new_commit = repo.find_object(new_commit_id, Some(git2::ObjectType::Commit))?;
+                let new_tag_id = match tag.tagger() {
+                    Some(tagger) => {
+                        let tagger = rewriter.rewrite_signature(tagger)?;
+                        repo.tag(tag.name().unwrap_or(""), &new_commit, &tagger, tag.message().unwrap_or(""), true)?
+                    },
+                    None => repo.tag_lightweight(tag.name().unwrap_or(""), &new_commit, true)?,
+                };
+                repo.reference(ref_name, new_tag_id, true, reflog_message)?;
+            },
+            Err(_) => {
+                repo.reference(ref_name, new_commit_id, true, reflog_message)?;
+            },
+        }
+    }
+    Ok(commit_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_git;
+
+    fn tree_paths(repo: &git2::Repository, commit_id: git2::Oid) -> Vec<String> {
+        let mut paths = vec![];
+        repo.find_commit(commit_id).expect("is commit").tree().expect("has tree")
+            .walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+                if entry.kind() == Some(git2::ObjectType::Blob) {
+                    paths.push(format!("{root}{name}", name = entry.name().unwrap_or("")));
+                }
+                git2::TreeWalkResult::Ok
+            }).expect("can walk");
+        paths.sort();
+        paths
+    }
+
+    // .env only / README & big file & docs tagged v1 / README changed by someone else
+    fn test_repo(dir: &std::path::Path) -> (git2::Repository, String, Vec<git2::Oid>) {
+        let repo = git2::Repository::init(dir).expect("can init repo");
+        let big_content = vec![1u8; 2000];
+        let old_signature = git2::Signature::now("Old Name", "old@example.com").expect("valid signature");
+        let other_signature = git2::Signature::now("Old Name", "other@example.com").expect("valid signature");
+        let commit_ids = vec![
+            test_git::commit_files(&repo, &[("secrets/.env", Some(b"TOKEN=x\n"))], &old_signature, "secrets"),
+            test_git::commit_files(&repo, &[
+                ("README.md", Some(b"# test\n")),
+                ("assets/big.bin", Some(&big_content)),
+                ("docs/a.md", Some(b"a\n")),
+            ], &old_signature, "content"),
+            test_git::commit_files(&repo, &[("README.md", Some(b"# test 2\n"))], &other_signature, "readme"),
+        ];
+        let tagged_commit = repo.find_object(commit_ids[1], None).expect("exists");
+        repo.tag("v1", &tagged_commit, &old_signature, "release", false).expect("can tag");
+        drop(tagged_commit);
+        let branch_ref_name = repo.head().expect("has head").name().expect("utf-8").to_string();
+        (repo, branch_ref_name, commit_ids)
+    }
+
+    #[test]
+    fn strips_paths_big_blobs_and_maps_authors() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let (repo, branch_ref_name, commit_ids) = test_repo(repo_dir.path());
+        let mut mailmap = git2::Mailmap::new().expect("can create mailmap");
+        mailmap.add_entry(Some("New Name"), Some("new@example.com"), None, "old@example.com").expect("can add entry");
+        let options = RewriteOptions {
+            strip_paths: vec!["/secrets/".to_string(), "".to_string()],
+            strip_blobs_bigger_than: Some(1000),
+            mailmap_path: None,
+        };
+        let commit_map = rewrite_history(&repo, &[branch_ref_name.as_str(), "refs/tags/v1"], &options, Some(&mailmap)).expect("can rewrite");
+        assert_eq!(commit_map.iter().map(|(old, _)| *old).collect::<Vec<_>>(), commit_ids);
+
+        let new_head = repo.find_reference(&branch_ref_name).expect("exists").peel_to_commit().expect("is commit");
+        let new_ids: Vec<git2::Oid> = commit_map.iter().map(|(_, new)| *new).collect();
+        assert_eq!(new_head.id(), new_ids[2]);
+        // root commit had only stripped files, empty tree is kept so history stays the same length
+        assert!(tree_paths(&repo, new_ids[0]).is_empty());
+        assert_eq!(tree_paths(&repo, new_ids[1]), vec!["README.md", "docs/a.md"]);
+        assert_eq!(tree_paths(&repo, new_ids[2]), vec!["README.md", "docs/a.md"]);
+        assert_eq!(new_head.parent_ids().collect::<Vec<_>>(), vec![new_ids[1]]);
+        assert_eq!(new_head.message(), Some("readme"));
+        assert_eq!(new_head.author().email(), Some("other@example.com"));
+        let content_commit = repo.find_commit(new_ids[1]).expect("is commit");
+        assert_eq!((content_commit.author().name(), content_commit.committer().email()), (Some("New Name"), Some("new@example.com")));
+
+        // old refs are backed up, annotated tag is recreated on the new commit
+        let original_ref = format!("refs/original/{branch_ref_name}");
+        assert_eq!(repo.refname_to_id(&original_ref).expect("backed up"), commit_ids[2]);
+        let tag = repo.find_reference("refs/tags/v1").expect("exists").peel_to_tag().expect("annotated");
+        assert_eq!((tag.target_id(), tag.message()), (new_ids[1], Some("release")));
+        assert_eq!(tag.tagger().expect("has tagger").email(), Some("new@example.com"));
+        assert!(repo.find_reference("refs/original/refs/tags/v1").is_ok());
+    }
+
+    #[test]
+    fn nothing_to_rewrite_keeps_refs() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let (repo, branch_ref_name, commit_ids) = test_repo(repo_dir.path());
+        let options = RewriteOptions { strip_paths: vec!["missing".to_string()], ..Default::default() };
+        let commit_map = rewrite_history(&repo, &[branch_ref_name.as_str()], &options, None).expect("can rewrite");
+        assert!(commit_map.is_empty());
+        assert_eq!(repo.refname_to_id(&branch_ref_name).expect("exists"), commit_ids[2]);
+        assert!(repo.find_reference(&format!("refs/original/{branch_ref_name}")).is_err());
+    }
+
+    #[test]
+    fn rewrite_options_is_empty() {
+        assert!(RewriteOptions::default().is_empty());
+        assert!(!RewriteOptions { strip_blobs_bigger_than: Some(1), ..Default::default() }.is_empty());
+    }
+}
@@ -0,0 +1,83 @@
+// Offline copies of the common GitHub .gitignore & license templates
+
+pub struct BundledLicense {
+    pub key: &'static str,
+    pub spdx_id: &'static str,
+    pub name: &'static str,
+    pub body: &'static str,
+}
+
+pub const bundled_gitignores: [(&str, &str); 4] = [
+    ("Go", include_str!("templates/gitignore/Go.gitignore")),
+    ("Node", include_str!("templates/gitignore/Node.gitignore")),
+    ("Python", include_str!("templates/gitignore/Python.gitignore")),
+    ("Rust", include_str!("templates/gitignore/Rust.gitignore")),
+];
+
+pub const bundled_licenses: [BundledLicense; 6] = [
+    BundledLicense { key: "apache-2.0", spdx_id: "Apache-2.0", name: "Apache License 2.0", body: include_str!("templates/licenses/apache-2.0.txt") },
+    BundledLicense { key: "bsd-3-clause", spdx_id: "BSD-3-Clause", name: "BSD 3-Clause \"New\" or \"Revised\" License", body: include_str!("templates/licenses/bsd-3-clause.txt") },
+    BundledLicense { key: "gpl-3.0", spdx_id: "GPL-3.0", name: "GNU General Public License v3.0", body: include_str!("templates/licenses/gpl-3.0.txt") },
+    BundledLicense { key: "mit", spdx_id: "MIT", name: "MIT License", body: include_str!("templates/licenses/mit.txt") },
+    BundledLicense { key: "mpl-2.0", spdx_id: "MPL-2.0", name: "Mozilla Public License 2.0", body: include_str!("templates/licenses/mpl-2.0.txt") },
+    BundledLicense { key: "unlicense", spdx_id: "Unlicense", name: "The Unlicense", body: include_str!("templates/licenses/unlicense.txt") },
+];
+
+pub fn bundled_gitignore(name: &str) -> Option<&'static str> {
+    bundled_gitignores.iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, source)| *source)
+}
+
+pub fn bundled_license(key_or_spdx_id: &str) -> Option<&'static BundledLicense> {
+    bundled_licenses.iter()
+        .find(|l| l.key.eq_ignore_ascii_case(key_or_spdx_id) || l.spdx_id.eq_ignore_ascii_case(key_or_spdx_id))
+}
+
+// Same placeholders GitHub fills when a license is picked on the website, they only appear in
+// copyright lines (MIT, BSD, ISC...). Apache "[yyyy] [name of copyright owner]" is a how-to-apply example, kept as-is
+pub fn license_fill(body: &str, year: i32, holder: &str) -> String {
+    body.replace("[year]", &year.to_string())
+        .replace("[fullname]", holder)
+}
+
+// Gitignore template name for project language from project::metadata
+pub fn gitignore_for_language(language: &str) -> Option<&'static str> {
+    match language {
+        "rust" => Some("Rust"),
+        "javascript" | "typescript" => Some("Node"),
+        "python" => Some("Python"),
+        "go" => Some("Go"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn license_fill_copyright_line() {
+        let mit = bundled_license("MIT").unwrap();
+        let filled = license_fill(mit.body, 2024, "Jane Doe");
+        assert!(filled.contains("Copyright (c) 2024 Jane Doe"));
+        assert!(!filled.contains("[year]") && !filled.contains("[fullname]"));
+    }
+
+    #[test]
+    fn license_fill_keeps_apache_appendix() {
+        let apache = bundled_license("apache-2.0").unwrap();
+        let filled = license_fill(apache.body, 2024, "Jane Doe");
+        assert_eq!(filled, apache.body);
+        assert!(filled.contains("Copyright [yyyy] [name of copyright owner]"));
+    }
+
+    #[test]
+    fn bundled_lookups() {
+        assert_eq!(bundled_license("bsd-3-clause").unwrap().spdx_id, "BSD-3-Clause");
+        assert!(bundled_license("wtfpl").is_none());
+        assert!(bundled_gitignore("rust").is_some());
+        assert_eq!(gitignore_for_language("typescript"), Some("Node"));
+        assert_eq!(gitignore_for_language("haskell"), None);
+    }
+}
@@ -0,0 +1,315 @@
+use super::{GhRsError, GhRsResult, lfs};
+use std::{
+    collections::HashSet,
+    sync::OnceLock,
+};
+use regex::Regex;
+
+// Pre-publish secret scanning of working tree changes & history being pushed
+
+const blob_size_max: usize = 1024 * 1024;
+const entropy_min: f64 = 4.5;
+const entropy_len_min: usize = 32;
+// integrity hashes there look exactly like random keys
+const entropy_skip_files: [&str; 6] = ["Cargo.lock", "package-lock.json", "yarn.lock", "pnpm-lock.yaml", "poetry.lock", "go.sum"];
+
+#[derive(Debug, Clone)]
+pub struct SecretRule {
+    pub name: String,
+    pub regex: Regex,
+}
+
+#[derive(Debug, Clone)]
+pub struct SecretFinding {
+    pub rule_name: String,
+    pub path: String,
+    pub line: usize,
+    pub commit_id: Option<git2::Oid>,
+    pub excerpt: String,
+}
+
+pub fn builtin_rules() -> Vec<SecretRule> {
+    [
+        ("GitHub token", r"\b(gh[pousr]_[A-Za-z0-9]{36,255})\b"),
+        ("GitHub fine-grained token", r"\b(github_pat_[A-Za-z0-9_]{22,255})\b"),
+        ("AWS access key id", r"\b((?:AKIA|ASIA)[0-9A-Z]{16})\b"),
+        ("AWS secret access key", r#"(?i)aws_?secret_?access_?key["']?\s*[:=]\s*["']?([A-Za-z0-9/+=]{40})\b"#),
+        ("Slack token", r"\b(xox[abposr]-[A-Za-z0-9-]{10,})\b"),
+        ("Slack webhook", r"(https://hooks\.slack\.com/services/[A-Za-z0-9_/]+)"),
+        ("Private key", r"(-----BEGIN (?:[A-Z]+ )?PRIVATE KEY(?: BLOCK)?-----)"),
+    ].into_iter()
+        .map(|(name, regex)| SecretRule { name: name.to_string(), regex: Regex::new(regex).expect("Valid regex") })
+        .collect()
+}
+
+// User rules are regexes, first capture group (or whole match) is the secret
+pub fn user_rules(rules_raw: &[(String, String)]) -> GhRsResult<Vec<SecretRule>> {
+    let mut rules = vec![];
+    for (name, regex_raw) in rules_raw {
+        match Regex::new(regex_raw) {
+            Ok(regex) => rules.push(SecretRule { name: name.clone(), regex }),
+            Err(e) => return_cmd_err!("Invalid secret rule \"{name}\", error: {e}"),
+        }
+    }
+    Ok(rules)
+}
+
+fn entropy(text: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for ch in text.chars() {
+        *counts.entry(ch).or_insert(0usize) += 1;
+    }
+    let len = text.chars().count() as f64;
+    counts.values()
+        .map(|c| *c as f64 / len)
+        .map(|p| -p * p.log2())
+        .sum()
+}
+
+fn redact(secret: &str) -> String {
+    let visible: String = secret.chars().take(4).collect();
+    format!("{visible}{stars}", stars = "*".repeat(secret.chars().count().saturating_sub(4).min(16)))
+}
+
+fn entropy_regex() -> &'static Regex {
+    static entropy_regex: OnceLock<Regex> = OnceLock::new();
+    entropy_regex.get_or_init(|| Regex::new(r#"["'=:]\s*["']?([A-Za-z0-9+/_\-]{32,}={0,2})"#).expect("Valid regex"))
+}
+
+fn scan_text(
+    rules: &[SecretRule],
+    path: &str,
+    text: &str,
+    commit_id: Option<git2::Oid>,
+    findings: &mut Vec<SecretFinding>,
+) {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let check_entropy = !entropy_skip_files.contains(&file_name);
+    for (line_i, line) in text.lines().enumerate() {
+        let mut found_in_line = false;
+        for rule in rules {
+            for captures in rule.regex.captures_iter(line) {
+                let secret = captures.get(1).or(captures.get(0)).expect("has match").as_str();
+                findings.push(SecretFinding {
+                    rule_name: rule.name.clone(),
+                    path: path.to_string(),
+                    line: line_i + 1,
+                    commit_id,
+                    excerpt: redact(secret),
+                });
+                found_in_line = true;
+            }
+        }
+        if found_in_line || !check_entropy { continue; }
+        for captures in entropy_regex().captures_iter(line) {
+            let candidate = captures.get(1).expect("has group").as_str();
+            if candidate.len() >= entropy_len_min && entropy(candidate) >= entropy_min {
+                findings.push(SecretFinding {
+                    rule_name: "High entropy string".to_string(),
+                    path: path.to_string(),
+                    line: line_i + 1,
+                    commit_id,
+                    excerpt: redact(candidate),
+                });
+            }
+        }
+    }
+}
+
+fn scan_blob_content(
+    rules: &[SecretRule],
+    path: &str,
+    content: &[u8],
+    commit_id: Option<git2::Oid>,
+    findings: &mut Vec<SecretFinding>,
+) {
+    if content.len() > blob_size_max { return; }
+    // binary files & LFS pointers (objects are scanned as big files, skip them too)
+    if content.iter().take(8000).any(|b| *b == 0) { return; }
+    if lfs::pointer_parse(content).is_some() { return; }
+    if let Ok(text) = std::str::from_utf8(content) {
+        scan_text(rules, path, text, commit_id, findings);
+    }
+}
+
+// Files relative to working tree
+pub fn scan_files(
+    repo: &git2::Repository,
+    rules: &[SecretRule],
+    paths: &[String],
+) -> GhRsResult<Vec<SecretFinding>> {
+    let mut findings = vec![];
+    let workdir = match repo.workdir() {
+        Some(w) => w,
+        None => return Ok(findings),
+    };
+    for path in paths {
+        let file_path = workdir.join(path);
+        if !file_path.is_file() { continue; }
+        if let Ok(content) = std::fs::read(&file_path) {
+            scan_blob_content(rules, path, &content, None, &mut findings);
+        }
+    }
+    Ok(findings)
+}
+
+// Changed & untracked (not ignored) files, i.e. what `add_all` would commit
+pub fn scan_worktree(
+    repo: &git2::Repository,
+    rules: &[SecretRule],
+) -> GhRsResult<Vec<SecretFinding>> {
+    let mut status_options = git2::StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false)
+        .exclude_submodules(true);
+    let statuses = repo.statuses(Some(&mut status_options))?;
+    let paths: Vec<String> = statuses.iter()
+        .filter(|e| !e.status().intersects(git2::Status::WT_DELETED | git2::Status::INDEX_DELETED))
+        .filter_map(|e| e.path().map(|p| p.to_string()))
+        .collect();
+    scan_files(repo, rules, &paths)
+}
+
+fn scan_tree(
+    repo: &git2::Repository,
+    rules: &[SecretRule],
+    tree: &git2::Tree,
+    tree_path: &str,
+    commit_id: git2::Oid,
+    seen_ids: &mut HashSet<git2::Oid>,
+    findings: &mut Vec<SecretFinding>,
+) -> GhRsResult<()> {
+    for entry in tree.iter() {
+        if !seen_ids.insert(entry.id()) { continue; }
+        let path = format!("{tree_path}{name}", name = entry.name().unwrap_or(""));
+        match entry.kind() {
+            Some(git2::ObjectType::Tree) => {
+                let subtree = repo.find_tree(entry.id())?;
+                scan_tree(repo, rules, &subtree, &format!("{path}/"), commit_id, seen_ids, findings)?;
+            },
+            Some(git2::ObjectType::Blob) => {
+                let blob = repo.find_blob(entry.id())?;
+                scan_blob_content(rules, &path, blob.content(), Some(commit_id), findings);
+            },
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+// Every blob reachable from refs, reported once with the oldest commit seen in
+pub fn scan_history(
+    repo: &git2::Repository,
+    rules: &[SecretRule],
+    ref_names: &[&str],
+) -> GhRsResult<Vec<SecretFinding>> {
+    let mut findings = vec![];
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    for ref_name in ref_names {
+        // annotated tags point to tag objects, walk their commits
+        revwalk.push(repo.find_reference(ref_name)?.peel_to_commit()?.id())?;
+    }
+    let mut seen_ids = HashSet::new();
+    for commit_id in revwalk {
+        let commit_id = commit_id?;
+        let tree = repo.find_commit(commit_id)?.tree()?;
+        if !seen_ids.insert(tree.id()) { continue; }
+        scan_tree(repo, rules, &tree, "", commit_id, &mut seen_ids, &mut findings)?;
+    }
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_git;
+
+    // built at runtime, so the test itself doesn't look like it leaks secrets
+    fn fake_github_token() -> String {
+        format!("ghp_{body}", body = "a1B2c3D4e5".repeat(4))
+    }
+
+    fn scan_line(line: &str, path: &str) -> Vec<SecretFinding> {
+        let mut findings = vec![];
+        scan_text(&builtin_rules(), path, line, None, &mut findings);
+        findings
+    }
+
+    #[test]
+    fn builtin_rules_match() {
+        let cases = [
+            ("GitHub token", format!("token = {token}", token = fake_github_token())),
+            ("GitHub fine-grained token", format!("github_pat_{body}", body = "x".repeat(30))),
+            ("AWS access key id", format!("AKIA{body}", body = "ABCDEFGH12345678")),
+            ("AWS secret access key", format!("aws_secret_access_key = {key}", key = "abcdEFGH1234/+".repeat(3).chars().take(40).collect::<String>())),
+            ("Slack token", format!("xoxb-{body}", body = "1234567890-abc")),
+            ("Slack webhook", format!("https://hooks.slack.com/services/{path}", path = "T000/B000/XXXX")),
+            ("Private key", format!("-----BEGIN {kind} PRIVATE KEY-----", kind = "RSA")),
+        ];
+        for (rule_name, line) in cases {
+            let findings = scan_line(&line, "config.txt");
+            assert_eq!(findings.iter().map(|f| f.rule_name.as_str()).collect::<Vec<_>>(), vec![rule_name], "{line}");
+        }
+        assert!(scan_line("let version = \"1.2.3\";", "main.rs").is_empty());
+    }
+
+    #[test]
+    fn findings_are_redacted() {
+        let findings = scan_line(&format!("\n\nGH_TOKEN={token}", token = fake_github_token()), ".env");
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].line, findings[0].excerpt.as_str()), (3, "ghp_****************"));
+        assert_eq!(redact("abc"), "abc");
+    }
+
+    #[test]
+    fn entropy_check() {
+        assert_eq!(entropy("aaaa"), 0.0);
+        assert!((entropy("abcd") - 2.0).abs() < 1e-9);
+        let random_key = "q8Zr2Lm5Xv9Tb1Nc7Wd3Kf6Hj0Gp4Ys8Ue";
+        assert!(entropy(random_key) >= entropy_min);
+        let findings = scan_line(&format!("api_key: \"{random_key}\""), "settings.yaml");
+        assert_eq!(findings.iter().map(|f| f.rule_name.as_str()).collect::<Vec<_>>(), vec!["High entropy string"]);
+        // lock file hashes & low entropy strings are fine
+        assert!(scan_line(&format!("checksum = \"{random_key}\""), "Cargo.lock").is_empty());
+        assert!(scan_line(&format!("value = \"{repeated}\"", repeated = "ab".repeat(20)), "a.txt").is_empty());
+    }
+
+    #[test]
+    fn user_rules_validated() {
+        let rules = user_rules(&[("Internal".to_string(), r"\b(int_[a-z0-9]{8})\b".to_string())]).expect("valid rule");
+        let mut findings = vec![];
+        scan_text(&rules, "a.txt", "key int_abcd1234 here", None, &mut findings);
+        assert_eq!((findings.len(), findings[0].excerpt.as_str()), (1, "int_********"));
+        let error = user_rules(&[("Broken".to_string(), "(".to_string())]).expect_err("invalid regex");
+        assert!(error.to_string().contains("Broken"), "{error}");
+    }
+
+    #[test]
+    fn scan_worktree_and_history() {
+        let repo_dir = tempfile::tempdir().expect("can create temp dir");
+        let repo = git2::Repository::init(repo_dir.path()).expect("can init repo");
+        let signature = test_git::signature();
+        let leaking_content = format!("TOKEN={token}\n", token = fake_github_token());
+        let leaking_commit_id = test_git::commit_files(&repo, &[("config.env", Some(leaking_content.as_bytes()))], &signature, "add config");
+        test_git::commit_files(&repo, &[("config.env", Some(b"TOKEN=\n"))], &signature, "remove token");
+
+        // removed from worktree, still in history
+        let rules = builtin_rules();
+        assert!(scan_worktree(&repo, &rules).expect("can scan").is_empty());
+        let history_findings = scan_history(&repo, &rules, &["HEAD"]).expect("can scan");
+        assert_eq!(history_findings.len(), 1);
+        assert_eq!((history_findings[0].path.as_str(), history_findings[0].commit_id), ("config.env", Some(leaking_commit_id)));
+
+        // untracked & ignored files, binary content
+        std::fs::write(repo_dir.path().join(".gitignore"), "ignored.txt\n").expect("can write file");
+        std::fs::write(repo_dir.path().join("ignored.txt"), fake_github_token()).expect("can write file");
+        std::fs::write(repo_dir.path().join("binary.bin"), [fake_github_token().as_bytes(), &[0u8]].concat()).expect("can write file");
+        std::fs::create_dir(repo_dir.path().join("nested")).expect("can create dir");
+        std::fs::write(repo_dir.path().join("nested/new.txt"), fake_github_token()).expect("can write file");
+        let worktree_findings = scan_worktree(&repo, &rules).expect("can scan");
+        assert_eq!(worktree_findings.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(), vec!["nested/new.txt"]);
+    }
+}
@@ -0,0 +1,419 @@
+use super::{GhRsError, GhRsResult, OctocrabReposCreateOptions, protect};
+use serde::{Serialize, Deserialize};
+use std::{
+    collections::BTreeMap,
+    path::Path,
+};
+
+// Repo settings-as-code for `settings plan/apply`, TOML or YAML by file extension:
+//   owner = "my-org"
+//   [[repos]]
+//   match = ["service-*", "gh0st-work/gh.rs"]
+//   topics = ["rust"]
+//   allow_merge_commit = false
+//   labels = [{ name = "bug", color = "d73a4a" }]
+//   collaborators = { octocat = "push" }
+//   [repos.branch_rules]
+//   required_reviews = 2
+// Every entry matching a repo applies, later entries override earlier ones
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SettingsFile {
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub repos: Vec<RepoSettings>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LabelSettings {
+    pub name: String,
+    pub color: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BranchRulesSettings {
+    // default branch when not set
+    pub branch: Option<String>,
+    pub required_reviews: Option<u32>,
+    pub dismiss_stale_reviews: Option<bool>,
+    pub code_owner_reviews: Option<bool>,
+    pub status_checks: Option<Vec<String>>,
+    pub strict_status_checks: Option<bool>,
+    pub linear_history: Option<bool>,
+    pub allow_force_pushes: Option<bool>,
+    pub allow_deletions: Option<bool>,
+    pub enforce_admins: Option<bool>,
+}
+
+impl BranchRulesSettings {
+    pub fn policy(&self, base_policy: &protect::ProtectionPolicy) -> protect::ProtectionPolicy {
+        let base_policy = base_policy.clone();
+        protect::ProtectionPolicy {
+            required_reviews: self.required_reviews.unwrap_or(base_policy.required_reviews),
+            dismiss_stale_reviews: self.dismiss_stale_reviews.unwrap_or(base_policy.dismiss_stale_reviews),
+            code_owner_reviews: self.code_owner_reviews.unwrap_or(base_policy.code_owner_reviews),
+            status_checks: self.status_checks.clone().unwrap_or(base_policy.status_checks),
+            strict_status_checks: self.strict_status_checks.unwrap_or(base_policy.strict_status_checks),
+            linear_history: self.linear_history.unwrap_or(base_policy.linear_history),
+            allow_force_pushes: self.allow_force_pushes.unwrap_or(base_policy.allow_force_pushes),
+            allow_deletions: self.allow_deletions.unwrap_or(base_policy.allow_deletions),
+            enforce_admins: self.enforce_admins.unwrap_or(base_policy.enforce_admins),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepoSettings {
+    // "name-glob" in file owner or "owner/name-glob", * and ? wildcards
+    #[serde(rename = "match")]
+    pub patterns: Vec<String>,
+    pub description: Option<String>,
+    pub topics: Option<Vec<String>>,
+    // homepage, has_*, is_template, merge options & visibility
+    #[serde(flatten)]
+    pub options: OctocrabReposCreateOptions,
+    pub labels: Option<Vec<LabelSettings>>,
+    pub prune_labels: Option<bool>,
+    // login -> pull, triage, push, maintain or admin
+    pub collaborators: Option<BTreeMap<String, String>>,
+    pub prune_collaborators: Option<bool>,
+    pub branch_rules: Option<BranchRulesSettings>,
+    // keys left over by options, deny_unknown_fields doesn't work along with flatten
+    #[serde(flatten, skip_serializing)]
+    unknown_keys: BTreeMap<String, serde::de::IgnoredAny>,
+}
+
+impl RepoSettings {
+    pub fn merge(&mut self, other: &RepoSettings) {
+        let options = other.options.clone();
+        self.description = other.description.clone().or(self.description.take());
+        self.topics = other.topics.clone().or(self.topics.take());
+        self.options = OctocrabReposCreateOptions {
+            homepage: options.homepage.or(self.options.homepage.take()),
+            has_issues: options.has_issues.or(self.options.has_issues),
+            has_projects: options.has_projects.or(self.options.has_projects),
+            has_wiki: options.has_wiki.or(self.options.has_wiki),
+            has_discussions: options.has_discussions.or(self.options.has_discussions),
+            is_template: options.is_template.or(self.options.is_template),
+            allow_merge_commit: options.allow_merge_commit.or(self.options.allow_merge_commit),
+            allow_squash_merge: options.allow_squash_merge.or(self.options.allow_squash_merge),
+            allow_rebase_merge: options.allow_rebase_merge.or(self.options.allow_rebase_merge),
+            delete_branch_on_merge: options.delete_branch_on_merge.or(self.options.delete_branch_on_merge),
+            allow_auto_merge: options.allow_auto_merge.or(self.options.allow_auto_merge),
+            visibility: options.visibility.or(self.options.visibility.take()),
+            ..Default::default()
+        };
+        self.labels = other.labels.clone().or(self.labels.take());
+        self.prune_labels = other.prune_labels.or(self.prune_labels);
+        self.collaborators = other.collaborators.clone().or(self.collaborators.take());
+        self.prune_collaborators = other.prune_collaborators.or(self.prune_collaborators);
+        self.branch_rules = other.branch_rules.clone().or(self.branch_rules.take());
+    }
+}
+
+pub fn load(path: &Path) -> GhRsResult<SettingsFile> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(t) => t,
+        Err(e) => return_cmd_err!("Failed to read {path_display}, error: {e}", path_display = path.display()),
+    };
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+    let settings_file: SettingsFile = match extension.as_str() {
+        "toml" => match toml::from_str(&text) {
+            Ok(s) => s,
+            Err(e) => return_cmd_err!("Invalid settings file, error: {e}"),
+        },
+        "yaml" | "yml" => match serde_yaml::from_str(&text) {
+            Ok(s) => s,
+            Err(e) => return_cmd_err!("Invalid settings file, error: {e}"),
+        },
+        _ => return_cmd_err!("Unknown settings file format \"{extension}\", expected .toml, .yaml or .yml"),
+    };
+    for repo_settings in &settings_file.repos {
+        if let Some(key) = repo_settings.unknown_keys.keys().next() {
+            return_cmd_err!("Invalid settings file, unknown key \"{key}\" in [[repos]] entry");
+        }
+        if repo_settings.patterns.is_empty() {
+            return_cmd_err!("Invalid settings file, every [[repos]] entry needs \"match\" patterns");
+        }
+        let is_owner_missing = repo_settings.patterns.iter().any(|p| !p.contains('/'));
+        if is_owner_missing && settings_file.owner.is_none() {
+            return_cmd_err!("Invalid settings file, set \"owner\" or use \"owner/name\" patterns");
+        }
+    }
+    Ok(settings_file)
+}
+
+// GitHub names are case insensitive
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    fn glob_match_chars(pattern: &[char], text: &[char]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some('*'), _) => glob_match_chars(&pattern[1..], text) || (!text.is_empty() && glob_match_chars(pattern, &text[1..])),
+            (Some('?'), Some(_)) => glob_match_chars(&pattern[1..], &text[1..]),
+            (Some(p), Some(t)) => p.eq_ignore_ascii_case(t) && glob_match_chars(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_chars(&pattern, &text)
+}
+
+// ("owner", "name-glob") of a pattern
+pub fn pattern_split<'p>(pattern: &'p str, owner_default: Option<&'p str>) -> (&'p str, &'p str) {
+    match pattern.split_once('/') {
+        Some((owner, name)) => (owner, name),
+        None => (owner_default.expect("checked on load"), pattern),
+    }
+}
+
+// Merged settings of every entry matching owner/name
+pub fn settings_for(settings_file: &SettingsFile, owner: &str, repo_name: &str) -> Option<RepoSettings> {
+    let mut merged_opt: Option<RepoSettings> = None;
+    for repo_settings in &settings_file.repos {
+        let is_match = repo_settings.patterns.iter().any(|p| {
+            let (pattern_owner, pattern_name) = pattern_split(p, settings_file.owner.as_deref());
+            pattern_owner.eq_ignore_ascii_case(owner) && glob_match(pattern_name, repo_name)
+        });
+        if !is_match { continue; }
+        match merged_opt.as_mut() {
+            Some(merged) => merged.merge(repo_settings),
+            None => merged_opt = Some(repo_settings.clone()),
+        }
+    }
+    merged_opt
+}
+
+// API reads "read" & "write", writes "pull" & "push"
+pub fn permission_normalize(permission: &str) -> String {
+    match permission.trim().to_lowercase().as_str() {
+        "read" => "pull".to_string(),
+        "write" => "push".to_string(),
+        p => p.to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SettingsAction {
+    UpdateRepo(serde_json::Map<String, serde_json::Value>),
+    ReplaceTopics(Vec<String>),
+    CreateLabel(LabelSettings),
+    UpdateLabel(String, LabelSettings),
+    DeleteLabel(String),
+    PutCollaborator(String, String),
+    RemoveCollaborator(String),
+    Protect(Option<String>, protect::ProtectionPolicy),
+}
+
+fn value_display(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "(none)".to_string(),
+        serde_json::Value::String(s) => format!("\"{s}\""),
+        v => v.to_string(),
+    }
+}
+
+// Field changes of repo itself, (field, live, desired)
+pub fn repo_field_changes(
+    repo_settings: &RepoSettings,
+    live_repo: &serde_json::Value,
+) -> Vec<(String, serde_json::Value, serde_json::Value)> {
+    let mut desired = match serde_json::to_value(&repo_settings.options) {
+        Ok(serde_json::Value::Object(m)) => m,
+        _ => serde_json::Map::new(),
+    };
+    if let Some(description) = &repo_settings.description {
+        desired.insert("description".to_string(), serde_json::json!(description));
+    }
+    let mut changes = vec![];
+    for (field, desired_value) in desired {
+        let live_value = live_repo.get(&field).cloned().unwrap_or(serde_json::Value::Null);
+        // unset description/homepage come back as null or ""
+        let is_same = live_value == desired_value
+            || (live_value.is_null() && desired_value.as_str() == Some(""));
+        if !is_same {
+            changes.push((field, live_value, desired_value));
+        }
+    }
+    changes
+}
+
+// Actions with lines describing them
+pub fn plan_repo(
+    repo_settings: &RepoSettings,
+    live_repo: &serde_json::Value,
+    live_topics: &[String],
+    live_labels: &[LabelSettings],
+    live_collaborators: &[(String, String)],
+) -> Vec<(SettingsAction, Vec<String>)> {
+    let mut actions = vec![];
+
+    let field_changes = repo_field_changes(repo_settings, live_repo);
+    if !field_changes.is_empty() {
+        let mut fields = serde_json::Map::new();
+        let mut lines = vec![];
+        for (field, live_value, desired_value) in field_changes {
+            lines.push(format!("{field}: {live} -> {desired}", live = value_display(&live_value), desired = value_display(&desired_value)));
+            fields.insert(field, desired_value);
+        }
+        actions.push((SettingsAction::UpdateRepo(fields), lines));
+    }
+
+    if let Some(topics) = &repo_settings.topics {
+        let mut topics_sorted: Vec<String> = topics.iter().map(|t| t.to_lowercase()).collect();
+        topics_sorted.sort();
+        let mut live_topics_sorted = live_topics.to_vec();
+        live_topics_sorted.sort();
+        if topics_sorted != live_topics_sorted {
+            actions.push((
+                SettingsAction::ReplaceTopics(topics_sorted.clone()),
+                vec![format!("topics: [{live}] -> [{desired}]", live = live_topics_sorted.join(", "), desired = topics_sorted.join(", "))],
+            ));
+        }
+    }
+
+    if let Some(labels) = &repo_settings.labels {
+        for label in labels {
+            let label = LabelSettings { color: label.color.trim_start_matches('#').to_lowercase(), ..label.clone() };
+            match live_labels.iter().find(|l| l.name.eq_ignore_ascii_case(&label.name)) {
+                None => actions.push((SettingsAction::CreateLabel(label.clone()), vec![format!("label {name}: create", name = label.name)])),
+                Some(live_label) => {
+                    let is_same = live_label.name == label.name
+                        && live_label.color.eq_ignore_ascii_case(&label.color)
+                        && (label.description.is_none() || live_label.description.as_deref().unwrap_or("") == label.description.as_deref().unwrap_or(""));
+                    if !is_same {
+                        actions.push((
+                            SettingsAction::UpdateLabel(live_label.name.clone(), label.clone()),
+                            vec![format!("label {name}: update", name = label.name)],
+                        ));
+                    }
+                },
+            }
+        }
+        if repo_settings.prune_labels == Some(true) {
+            for live_label in live_labels {
+                if !labels.iter().any(|l| l.name.eq_ignore_ascii_case(&live_label.name)) {
+                    actions.push((SettingsAction::DeleteLabel(live_label.name.clone()), vec![format!("label {name}: delete", name = live_label.name)]));
+                }
+            }
+        }
+    }
+
+    if let Some(collaborators) = &repo_settings.collaborators {
+        for (login, permission) in collaborators {
+            let permission = permission_normalize(permission);
+            match live_collaborators.iter().find(|(l, _)| l.eq_ignore_ascii_case(login)) {
+                None => actions.push((
+                    SettingsAction::PutCollaborator(login.clone(), permission.clone()),
+                    vec![format!("collaborator {login}: invite with {permission}")],
+                )),
+                Some((_, live_permission)) if *live_permission != permission => actions.push((
+                    SettingsAction::PutCollaborator(login.clone(), permission.clone()),
+                    vec![format!("collaborator {login}: {live_permission} -> {permission}")],
+                )),
+                Some(_) => {},
+            }
+        }
+        if repo_settings.prune_collaborators == Some(true) {
+            for (live_login, _) in live_collaborators {
+                if !collaborators.keys().any(|l| l.eq_ignore_ascii_case(live_login)) {
+                    actions.push((SettingsAction::RemoveCollaborator(live_login.clone()), vec![format!("collaborator {live_login}: remove")]));
+                }
+            }
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_text(file_name: &str, text: &str) -> GhRsResult<SettingsFile> {
+        let settings_dir = tempfile::tempdir().expect("can create temp dir");
+        let path = settings_dir.path().join(file_name);
+        std::fs::write(&path, text).expect("can write file");
+        load(&path)
+    }
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("service-*", "service-api"));
+        assert!(glob_match("service-*", "Service-"));
+        assert!(glob_match("gh.r?", "gh.rs"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "aXXbYc"));
+        assert!(!glob_match("service-*", "api-service"));
+        assert!(!glob_match("gh.r?", "gh.r"));
+    }
+
+    #[test]
+    fn load_toml_and_yaml() {
+        let settings_file = load_text("settings.toml", "owner = \"my-org\"\n[[repos]]\nmatch = [\"service-*\"]\nallow_merge_commit = false\n[repos.branch_rules]\nrequired_reviews = 2\n")
+            .expect("valid settings");
+        assert_eq!(settings_file.repos[0].options.allow_merge_commit, Some(false));
+        assert_eq!(settings_file.repos[0].branch_rules.as_ref().and_then(|b| b.required_reviews), Some(2));
+
+        let settings_file = load_text("settings.yml", "repos:\n  - match: [\"my-org/gh.rs\"]\n    topics: [rust]\n")
+            .expect("valid settings");
+        assert_eq!(settings_file.repos[0].topics, Some(vec!["rust".to_string()]));
+    }
+
+    #[test]
+    fn load_rejects_unknown_and_missing_keys() {
+        assert!(load_text("settings.toml", "owner = \"my-org\"\n[[repos]]\nmatch = [\"*\"]\nallow_merge_comit = false\n").is_err());
+        assert!(load_text("settings.yaml", "owner: my-org\nrepos:\n  - match: [\"*\"]\n    branch_rules: { required_review: 2 }\n").is_err());
+        assert!(load_text("settings.toml", "ownr = \"my-org\"\n").is_err());
+        // owner is needed for patterns without one
+        assert!(load_text("settings.toml", "[[repos]]\nmatch = [\"service-*\"]\n").is_err());
+        assert!(load_text("settings.json", "{}").is_err());
+    }
+
+    #[test]
+    fn settings_for_merges_matching_entries_in_order() {
+        let settings_file = load_text(
+            "settings.toml",
+            "owner = \"my-org\"\n[[repos]]\nmatch = [\"*\"]\ndescription = \"any\"\nhas_wiki = false\n[[repos]]\nmatch = [\"service-*\"]\ndescription = \"service\"\n",
+        ).expect("valid settings");
+        let merged = settings_for(&settings_file, "My-Org", "service-api").expect("has match");
+        assert_eq!(merged.description.as_deref(), Some("service"));
+        assert_eq!(merged.options.has_wiki, Some(false));
+        assert!(settings_for(&settings_file, "other-org", "service-api").is_none());
+    }
+
+    #[test]
+    fn plan_repo_actions() {
+        let repo_settings = RepoSettings {
+            patterns: vec!["*".to_string()],
+            description: Some("new".to_string()),
+            topics: Some(vec!["Rust".to_string(), "cli".to_string()]),
+            options: OctocrabReposCreateOptions { has_wiki: Some(false), ..Default::default() },
+            labels: Some(vec![
+                LabelSettings { name: "bug".to_string(), color: "#D73A4A".to_string(), description: None },
+                LabelSettings { name: "docs".to_string(), color: "0075ca".to_string(), description: None },
+            ]),
+            prune_labels: Some(true),
+            collaborators: Some(BTreeMap::from([("octocat".to_string(), "write".to_string())])),
+            ..Default::default()
+        };
+        let live_repo = serde_json::json!({ "description": "old", "has_wiki": false });
+        let live_labels = [
+            LabelSettings { name: "bug".to_string(), color: "d73a4a".to_string(), description: Some("Something isn't working".to_string()) },
+            LabelSettings { name: "wontfix".to_string(), color: "ffffff".to_string(), description: None },
+        ];
+        let live_collaborators = [("octocat".to_string(), "pull".to_string())];
+        let actions = plan_repo(&repo_settings, &live_repo, &["cli".to_string(), "rust".to_string()], &live_labels, &live_collaborators);
+        let lines: Vec<String> = actions.iter().flat_map(|(_, lines)| lines.clone()).collect();
+        assert_eq!(lines, [
+            "description: \"old\" -> \"new\"",
+            "label docs: create",
+            "label wontfix: delete",
+            "collaborator octocat: pull -> push",
+        ]);
+        assert!(matches!(&actions[0].0, SettingsAction::UpdateRepo(fields) if fields.len() == 1));
+    }
+}
@@ -0,0 +1,182 @@
+use super::{GhRsError, GhRsResult};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::OnceLock,
+};
+use regex::Regex;
+
+// User project templates for `new --template`, directories rendered into the new repo
+
+pub const manifest_file_name: &str = "gh.rs-template.toml";
+// builtin variables, always filled by gh.rs
+pub const builtin_variables: [&str; 4] = ["repo_name", "username", "description", "year"];
+
+#[derive(Debug, Clone)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub prompt: String,
+    pub default: Option<String>,
+}
+
+fn variable_regex() -> &'static Regex {
+    static variable_regex: OnceLock<Regex> = OnceLock::new();
+    variable_regex.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_]+)\s*\}\}").expect("Valid regex"))
+}
+
+// Custom variables from the manifest, in declaration order:
+//   [variables]
+//   crate_type = { prompt = "Crate type", default = "bin" }
+//   author = "Author name"
+pub fn variables(template_dir: &Path) -> GhRsResult<Vec<TemplateVariable>> {
+    let manifest_path = template_dir.join(manifest_file_name);
+    if !manifest_path.is_file() {
+        return Ok(vec![]);
+    }
+    let manifest = match std::fs::read_to_string(&manifest_path).map(|t| t.parse::<toml::Table>()) {
+        Ok(Ok(m)) => m,
+        Ok(Err(e)) => return_cmd_err!("Invalid {manifest_file_name}, error: {e}"),
+        Err(e) => return_cmd_err!("Failed to read {manifest_file_name}, error: {e}"),
+    };
+    let variables_table = match manifest.get("variables") {
+        Some(toml::Value::Table(t)) => t,
+        Some(_) => return_cmd_err!("Invalid {manifest_file_name}, \"variables\" must be a table"),
+        None => return Ok(vec![]),
+    };
+    let mut variables = vec![];
+    for (name, value) in variables_table {
+        if builtin_variables.contains(&name.as_str()) {
+            return_cmd_err!("Invalid {manifest_file_name}, \"{name}\" is a builtin variable");
+        }
+        let variable = match value {
+            toml::Value::String(prompt) => TemplateVariable { name: name.clone(), prompt: prompt.clone(), default: None },
+            toml::Value::Table(t) => TemplateVariable {
+                name: name.clone(),
+                prompt: t.get("prompt").and_then(|v| v.as_str()).unwrap_or(name).to_string(),
+                default: t.get("default").map(|v| match v {
+                    toml::Value::String(s) => s.clone(),
+                    v => v.to_string(),
+                }),
+            },
+            _ => return_cmd_err!("Invalid {manifest_file_name}, variable \"{name}\" must be a string or a table"),
+        };
+        variables.push(variable);
+    }
+    Ok(variables)
+}
+
+// Unknown variables are left as-is, templates may contain other {{ }} syntax
+pub fn render_text(text: &str, values: &HashMap<String, String>) -> String {
+    variable_regex().replace_all(text, |captures: &regex::Captures| {
+        match values.get(&captures[1]) {
+            Some(value) => value.clone(),
+            None => captures[0].to_string(),
+        }
+    }).to_string()
+}
+
+// Rendered names must stay a single path component inside target dir
+fn rendered_name_is_valid(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains('/') && !name.contains('\\')
+}
+
+// Renders file contents & names, binary files are copied as-is, symlinks are skipped. Returns count of files written
+pub fn render_dir(
+    template_dir: &Path,
+    target_dir: &Path,
+    values: &HashMap<String, String>,
+) -> std::io::Result<usize> {
+    let mut files_count = 0;
+    for entry in std::fs::read_dir(template_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name == ".git" || file_name == manifest_file_name {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            continue;
+        }
+        let rendered_name = render_text(&file_name, values);
+        if !rendered_name_is_valid(&rendered_name) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{file_name} renders to invalid file name \"{rendered_name}\""),
+            ));
+        }
+        let target_path = target_dir.join(rendered_name);
+        if file_type.is_dir() {
+            std::fs::create_dir_all(&target_path)?;
+            files_count += render_dir(&entry.path(), &target_path, values)?;
+            continue;
+        }
+        let content = std::fs::read(entry.path())?;
+        let is_binary = content.iter().take(8000).any(|b| *b == 0);
+        match std::str::from_utf8(&content) {
+            Ok(text) if !is_binary => std::fs::write(&target_path, render_text(text, values))?,
+            _ => std::fs::write(&target_path, &content)?,
+        }
+        // keep scripts executable
+        std::fs::set_permissions(&target_path, entry.metadata()?.permissions())?;
+        files_count += 1;
+    }
+    Ok(files_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn render_text_fills_known_variables_only() {
+        let values = values(&[("repo_name", "demo")]);
+        assert_eq!(render_text("# {{ repo_name }} by {{username}}", &values), "# demo by {{username}}");
+    }
+
+    #[test]
+    fn render_dir_renders_names_and_contents() {
+        let template_dir = tempfile::tempdir().expect("can create temp dir");
+        let target_dir = tempfile::tempdir().expect("can create temp dir");
+        std::fs::create_dir(template_dir.path().join("{{repo_name}}")).expect("can create dir");
+        std::fs::write(template_dir.path().join("{{repo_name}}/main.rs"), "// {{repo_name}}\n").expect("can write file");
+        std::fs::write(template_dir.path().join(manifest_file_name), "[variables]\n").expect("can write file");
+
+        let files_count = render_dir(template_dir.path(), target_dir.path(), &values(&[("repo_name", "demo")])).expect("can render");
+        assert_eq!(files_count, 1);
+        let content = std::fs::read_to_string(target_dir.path().join("demo/main.rs")).expect("rendered file exists");
+        assert_eq!(content, "// demo\n");
+        assert!(!target_dir.path().join(manifest_file_name).exists());
+    }
+
+    #[test]
+    fn render_dir_rejects_names_escaping_target() {
+        for value in ["../escape", "..", "", "a/b"] {
+            let template_dir = tempfile::tempdir().expect("can create temp dir");
+            let target_dir = tempfile::tempdir().expect("can create temp dir");
+            std::fs::write(template_dir.path().join("{{name}}"), "x").expect("can write file");
+            let error = render_dir(template_dir.path(), target_dir.path(), &values(&[("name", value)])).unwrap_err();
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn render_dir_skips_symlinks() {
+        let outside_dir = tempfile::tempdir().expect("can create temp dir");
+        let template_dir = tempfile::tempdir().expect("can create temp dir");
+        let target_dir = tempfile::tempdir().expect("can create temp dir");
+        std::fs::write(outside_dir.path().join("secret.txt"), "secret").expect("can write file");
+        std::os::unix::fs::symlink(outside_dir.path().join("secret.txt"), template_dir.path().join("link.txt")).expect("can symlink");
+        std::os::unix::fs::symlink(outside_dir.path(), template_dir.path().join("link_dir")).expect("can symlink");
+        std::fs::write(template_dir.path().join("README.md"), "hi").expect("can write file");
+
+        let files_count = render_dir(template_dir.path(), target_dir.path(), &HashMap::new()).expect("can render");
+        assert_eq!(files_count, 1);
+        assert!(!target_dir.path().join("link.txt").exists());
+        assert!(!target_dir.path().join("link_dir").exists());
+    }
+}
@@ -0,0 +1,42 @@
+// git2 fixtures shared by unit tests
+
+pub fn signature() -> git2::Signature<'static> {
+    git2::Signature::now("test", "test@example.com").expect("valid signature")
+}
+
+// Writes (Some) or removes (None) files in workdir & commits them on top of HEAD
+pub fn commit_files(
+    repo: &git2::Repository,
+    files: &[(&str, Option<&[u8]>)],
+    signature: &git2::Signature<'_>,
+    message: &str,
+) -> git2::Oid {
+    let workdir = repo.workdir().expect("has workdir");
+    let mut index = repo.index().expect("has index");
+    for (path, content_opt) in files {
+        let file_path = workdir.join(path);
+        match content_opt {
+            Some(content) => {
+                std::fs::create_dir_all(file_path.parent().expect("has parent")).expect("can create dir");
+                std::fs::write(&file_path, content).expect("can write file");
+                index.add_path(std::path::Path::new(path)).expect("can stage");
+            },
+            None => {
+                std::fs::remove_file(&file_path).expect("can remove file");
+                index.remove_path(std::path::Path::new(path)).expect("can unstage");
+            },
+        }
+    }
+    index.write().expect("can write index");
+    let tree = repo.find_tree(index.write_tree().expect("can write tree")).expect("tree exists");
+    let parents: Vec<git2::Commit> = repo.head().ok().and_then(|h| h.peel_to_commit().ok()).into_iter().collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(Some("HEAD"), signature, signature, message, &tree, &parent_refs).expect("can commit")
+}
+
+// New repo with README.md in a single commit
+pub fn repo_with_commit(dir: &std::path::Path) -> git2::Repository {
+    let repo = git2::Repository::init(dir).expect("can init repo");
+    commit_files(&repo, &[("README.md", Some(b"# test\n"))], &signature(), "init");
+    repo
+}